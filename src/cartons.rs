@@ -12,17 +12,37 @@
 //!   value matched with a unified dataset name(key).
 //! * [`CartonsMap`] is a helper data structure to store information of sizing rules and states.
 //! * [`CartonsMetric`] is deviated from `CartonsMap`.
+//! * [`CartonsTree`] nests complexes into each other, for docking layouts. => mod [`tree`]
+//! * [`CartonsState`] persists and restores a complex's layout state. => mod [`persist`]
+//! * Resizers are keyboard-operable, with ARIA separator semantics. => mod [`keyboard`]
+//! * Resizers' double-click can collapse/restore, reset or auto-fit cartons. => mod [`dblclick`]
+//! * Programmatic size changes can be animated. => mod [`animate`]
 
 use crate::*;
 
 pub mod resize;
 
+pub mod tree;
+pub use tree::*;
+
+pub mod persist;
+pub use persist::*;
+
+pub mod keyboard;
+pub use keyboard::*;
+
+pub mod dblclick;
+pub use dblclick::*;
+
+pub mod animate;
+pub use animate::*;
+
 
 /// Helper structure to store data of sizing rules and states.
 /// Generic `<T>` is a carton's dataset value type. Check [`parse_dataset()`] about this.
 /// * Field `map` stores specific carton's data.
 /// * If a carton's name is not in the `map`, get field `default`'s value as fallback.
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct CartonsMap<T: Eq + Hash + FromStr + Clone, V> {
   pub map: HashMap<T, V>,
   pub default: V
@@ -97,6 +117,25 @@ impl<T: Eq + Hash + FromStr + Clone, V> CartonsMap<T, V> {
 /// value None refers to "zeroed" state;
 pub type CartonsMetric<T> = CartonsMap<T, Option<Sizon>>;
 
+/// Outputs of resizing works: (new metric, changes of zeroed cache, zero-restored cartons).
+/// Check [`CartonsComplex::apply_resized()`]
+pub type ResizeOutput<T> = (CartonsMetric<T>, HashMap<T, f64>, HashSet<T>);
+
+/// Set cartons' size and front position style by their sizes, from `since` index.
+/// Positions follow the sizes, as cartons are arranged from the front.
+fn set_cartons_style<H: AsRef<HtmlElement>>(lateral: bool, elems: Vec<H>, sizes: impl Iterator<Item = f64>, since: usize) {
+  let (size_prop, pos_prop) = size_pos_props(lateral);
+  let mut pos = 0.;
+  elems.into_iter().zip(sizes).enumerate().for_each(|(i, (elem, size))| {
+    if i>=since {
+      let style = elem.as_ref().style();
+      style.set_property(size_prop, format!("{:.2}px", size).as_str()).unwrap_throw();
+      style.set_property(pos_prop, format!("{:.2}px", pos).as_str()).unwrap_throw();
+    }
+    pos += size;
+  });
+}
+
 impl<T: Eq + Hash + FromStr + Clone> CartonsMetric<T> {
 
   /// Return new CartonsMetric, cloning self but revises only Sizon's `abs` field, keeps `rel`.
@@ -175,7 +214,25 @@ pub struct CartonsComplex<T: Eq + Hash + FromStr + Clone> {
   ///     then the carton's size will be restored to its minimum threshold, 30.px.
   pub zeroed_when: CartonsMap<T, Sizon>,
   /// cache former size's ratio
-  pub zeroed_cache: CartonsMap<T, f64>
+  pub zeroed_cache: CartonsMap<T, f64>,
+  /// which box of the wrap to measure for the total space. (See [`MeasureBox`])
+  /// * Default is `Content`, as cartons are laid out inside the wrap's padding, borders and scrollbar.
+  ///   Use `Padding` when cartons cover the wrap's padding too.
+  pub wrap_box: MeasureBox,
+  /// which box of cartons to measure. (See [`MeasureBox`])
+  /// * Cartons' size style(`width`/`height`) is written with this measurement.
+  ///   Thus it should match with cartons' `box-sizing`: `Border`(default) for border-box, `Content` for content-box.
+  pub carton_box: MeasureBox,
+  /// steps of keyboard resizing. (See [`KeyStep`])
+  pub key_step: KeyStep,
+  /// action of resizers' double-click. (See [`DblClickAction`])
+  /// * Default is None, doing nothing.
+  pub dblclick: Option<DblClickAction>,
+  /// initial metric given to `new()`, which `DblClickAction::Reset` resets to.
+  pub initial_metric: CartonsMetric<T>,
+  /// transition of resizers' discrete changes: `Enter` key and double-click. (See [`CartonsTransition`])
+  /// * Default is None, jumping straight to new sizes.
+  pub transition: Option<CartonsTransition>,
 }
 
 
@@ -195,7 +252,12 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
     zeroed_cache: CartonsMap<T, f64>
   ) -> Self {
     let name = name.unwrap_or("carton");
-    Self { lateral, independent, name, metric, min, max, allow_zero, zeroed_when, zeroed_cache }
+    let initial_metric = metric.clone();
+    Self {
+      lateral, independent, name, metric, min, max, allow_zero, zeroed_when, zeroed_cache,
+      wrap_box: MeasureBox::Content, carton_box: MeasureBox::Border, key_step: KeyStep::default(),
+      dblclick: None, initial_metric, transition: None
+    }
   }
 
   /// Make lists of carton element and dataset value, from given `wrap` element and dataset `name`
@@ -212,48 +274,27 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
 
   /// return max limited size 
   fn max_limited(&self, data: &T, size: f64, wrap_size: f64) -> f64 {
-    self.max.get(data).min(size, Some(wrap_size))
+    self.space_item(data, Some(size)).max_limited(size, wrap_size)
   }
 
   /// return min and max limited size
   fn limited(&self, data: &T, size: f64, wrap_size: f64) -> f64 {
-    let size = self.max.get(data).min(size, Some(wrap_size));
-    self.min.get(data).max(size, Some(wrap_size))
+    self.space_item(data, Some(size)).limited(size, wrap_size)
   }
 
   /// return possible max limitation size
   fn _max(&self, data: &T, wrap_size: f64) -> Option<f64> {
-    let sizon = self.max.get(data);
-
-    let _max = if let Some(abs) = sizon.abs {
-      if let Some(rel) = sizon.rel {
-        abs.min(rel*wrap_size) // use .min(), cuz it's for limitation check
-      } else {
-        abs
-      }
-    } else if let Some(rel) = sizon.rel {
-      rel*wrap_size
-    } else {
-      return None;
-    };
-    Some(_max)
+    self.max.get(data).max_limit(wrap_size)
   }
 
   /// return possible min limitation size. fallback is zero.
   fn _min(&self, data: &T, wrap_size: f64) -> f64 {
-    let sizon = self.min.get(data);
+    self.min.get(data).min_limit(wrap_size)
+  }
 
-    if let Some(abs) = sizon.abs {
-      if let Some(rel) = sizon.rel {
-        abs.max(rel*wrap_size) // use .max(), as it's for limitation check
-      } else {
-        abs
-      }
-    } else if let Some(rel) = sizon.rel {
-      rel*wrap_size
-    } else {
-      0.
-    }
+  /// Build a [`SpaceItem`] of a carton. Its current size is its weight.
+  fn space_item(&self, data: &T, size: Option<f64>) -> SpaceItem {
+    SpaceItem::new(size, *self.min.get(data), *self.max.get(data), size.unwrap_or_default())
   }
 
   /// Return `zeroed_when` threshold value.
@@ -308,49 +349,18 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
   /// Return adjusted total_size.
   /// 
   /// Not independent cartons will use it for wrapping level sizing and resizer's resizing.
+  /// Check [`SpaceSolver::fill_blank()`].
   fn adjust_to_fill_blank(
     &self,
     wrap_size: f64,
-    data_sizes: &mut Vec<(T, Option<f64>)>
+    data_sizes: &mut [(T, Option<f64>)]
   ) -> f64 {
-    let mut total_size = Self::get_total_size(data_sizes);
-
-    let mut blank = (wrap_size-total_size).floor();
-    if blank>0. {
-      // 1. proportional distribution
-      for (data, size) in data_sizes.iter_mut() {
-        if blank>0. {
-          if let Some(size) = size {
-            let add = *size/wrap_size * blank;
-            let new_size = self.max_limited(data, *size+add, wrap_size);
-            let delta = (new_size-*size).max(0.);
-            total_size += delta;
-            blank -= delta;
-            *size += delta;
-          }
-        } else {
-          break
-        }
-      }
+    let items: Vec<SpaceItem> = data_sizes.iter().map(|(data, size)| self.space_item(data, *size)).collect();
+    let mut sizes: Vec<Option<f64>> = items.iter().map(|item| item.size).collect();
 
-      // 2. distribute from rear ones
-      if blank>0. {
-        for (data, size) in data_sizes.iter_mut().rev() {
-          if blank>0. {
-            if let Some(size) = size {
-              let new_size = self.max_limited(data, *size+blank, wrap_size);
-              let delta = (new_size-*size).max(0.);
-              total_size += delta;
-              blank -= delta;
-              *size += delta;
-            } 
-          } else {
-            break;
-          }
-        }
-      }
-    }
+    let total_size = SpaceSolver::new(wrap_size).fill_blank(&items, &mut sizes);
 
+    data_sizes.iter_mut().zip(sizes).for_each(|((_, size), new_size)| *size = new_size);
     total_size
   }
 
@@ -364,17 +374,7 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
     data_sizes: &Vec<(T, Option<f64>)>,
     since: usize
   ) {
-    let (size_prop, pos_prop) = size_pos_props(self.lateral);
-    let mut pos = 0.;
-    elems.into_iter().zip(data_sizes.iter()).enumerate().for_each(|(i, (elem, (.., size)))| {
-      let size = size.unwrap_or(0.);
-      if i>=since {
-        let style = elem.as_ref().style();
-        style.set_property(size_prop, format!("{:.2}px", size).as_str()).unwrap_throw();
-        style.set_property(pos_prop, format!("{:.2}px", pos).as_str()).unwrap_throw();
-      }
-      pos += size;
-    });
+    set_cartons_style(self.lateral, elems, data_sizes.iter().map(|(_, size)| size.unwrap_or(0.)), since);
   }
 
   /// Wrapping level's effect on any possible update (such as initiation and window's resizing).
@@ -393,7 +393,13 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
   ) -> Result<CartonsMetric<T>> {
 
     let Some(wrap) = get_elem(wrap) else { return Err(Error::Ignore) };
-    let wrap_size = get_client_size(&wrap, self.lateral);
+    Ok(self.wrap_effect(wrap))
+  }
+
+  /// Inner work of [`wrap_effect_on_update()`] with the wrap element
+  fn wrap_effect<E: AsRef<Element>>(&self, wrap: E) -> CartonsMetric<T> {
+
+    let wrap_size = get_box_size(&wrap, self.lateral, self.wrap_box);
 
     let (elems, datas) = Self::wrap_to_carton_elems(&wrap, self.name);
 
@@ -420,8 +426,7 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
 
     self.update_style(elems, &data_sizes, 0);
 
-    let metric = self.metric.abs_revised(data_sizes, total_size);
-    Ok(metric)
+    self.metric.abs_revised(data_sizes, total_size)
   }
 
   /// Exapnd [`wrap_effect_on_update()`] for ready made use in Sycamore
@@ -524,7 +529,7 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
     data: &T
   ) -> Result<(f64, Vec<HtmlElement>, Vec<(T, Option<f64>)>, usize, Option<f64>)> {
 
-    let wrap_size = get_client_size(wrap.as_ref(), self.lateral);
+    let wrap_size = get_box_size(wrap.as_ref(), self.lateral, self.wrap_box);
     
     let elems = wrap.as_ref().children();
     
@@ -535,7 +540,7 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
           let size = if self._zeroed(&data) {
             None
           } else {
-            Some(get_elem_size(&carton, self.lateral)) // DO NOT use client size. USE DomRect size.
+            Some(get_box_size(&carton, self.lateral, self.carton_box)) // DO NOT use client size. USE DomRect based size.
           };
           Some((carton, (data, size)))
         } else {
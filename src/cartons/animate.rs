@@ -0,0 +1,408 @@
+//! Animated transitions of programmatic size changes
+//!
+//! [`CartonsComplex::switch_zero()`] and [`CartonsComplex::wrap_effect_on_update()`] jump straight to new sizes.
+//! CSS transitions on `width`/`left` don't help, as they also apply during drag-resizing.
+//!
+//! [`CartonsTransition`] animates cartons' size and position style with `requestAnimationFrame` instead,
+//! only for the changes it drives:
+//! * [`CartonsComplex::transit()`] measures cartons before and after a work, then interpolates between them.
+//! * [`CartonsComplex::animated_wrap_effect_on_update()`] and [`CartonsComplex::animated_switch_zero()`] are ready-made ones.
+//!   Sycamore ones are `animate_wrap()` and `animate_switch_zero()`. (*feature `sycamore`*)
+//! * Resizers of `CartonsComplex::init_resizer()` animate their `Enter` key and double-click with the complex's field `transition`.
+//!   Drag-resizing and arrow keys are never animated. (*feature `sycamore`*)
+//!   The running one is kept in an [`AnimationSlot`]: pointerdown cancels it, and a new keydown or double-click finishes it first.
+//!
+//! The final metric is emitted only when the animation completes.
+//! With `prefers-reduced-motion: reduce`, it jumps to the end at once.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! assert_eq!(Easing::Linear.ease(0.25), 0.25);
+//! assert_eq!(Easing::EaseOut.ease(0.5), 0.875);
+//! assert_eq!(CartonsTransition::interpolate(&[100., 300.], &[0., 400.], 0.25), vec![75., 325.]);
+//! ```
+
+use super::*;
+use std::{cell::{Cell, RefCell}, rc::Rc};
+
+/// Easing functions of transitions
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Easing {
+  Linear,
+  /// cubic ease-in
+  EaseIn,
+  /// cubic ease-out
+  EaseOut,
+  /// cubic ease-in-out
+  #[default]
+  EaseInOut,
+}
+
+impl Easing {
+
+  /// Eased progress of linear progress `t`, from 0. to 1.
+  pub fn ease(&self, t: f64) -> f64 {
+    let t = t.clamp(0., 1.);
+    match self {
+      Self::Linear => t,
+      Self::EaseIn => t*t*t,
+      Self::EaseOut => 1.-(1.-t).powi(3),
+      Self::EaseInOut => if t<0.5 { 4.*t*t*t } else { 1.-(-2.*t+2.).powi(3)/2. },
+    }
+  }
+}
+
+/// Transition of cartons' sizes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CartonsTransition {
+  /// duration in milliseconds
+  pub duration: f64,
+  pub easing: Easing,
+}
+
+impl Default for CartonsTransition {
+  fn default() -> Self {
+    Self { duration: 200., easing: Easing::default() }
+  }
+}
+
+impl CartonsTransition {
+
+  pub fn new(duration: f64, easing: Easing) -> Self {
+    Self { duration, easing }
+  }
+
+  /// Does the user prefer reduced motion? (`prefers-reduced-motion: reduce`)
+  pub fn reduced_motion() -> bool {
+    gloo_utils::window().match_media("(prefers-reduced-motion: reduce)").ok().flatten()
+      .map(|x| x.matches()).unwrap_or(false)
+  }
+
+  /// Sizes interpolated between `from` and `to` at (eased) progress `t`
+  pub fn interpolate(from: &[f64], to: &[f64], t: f64) -> Vec<f64> {
+    from.iter().zip(to.iter()).map(|(from, to)| from+(to-from)*t).collect()
+  }
+
+  /// Animate size and position style of carton elements from `from` sizes to `to` sizes.
+  /// Positions follow the sizes, like cartons are arranged from the front.
+  ///
+  /// The `from` style is set at once, and `done` is called after the `to` style is set.
+  /// Without duration or with reduced motion, it jumps to the end at once.
+  pub fn animate<H: AsRef<HtmlElement> + 'static>(
+    &self,
+    lateral: bool,
+    elems: Vec<H>,
+    from: Vec<f64>,
+    to: Vec<f64>,
+    done: impl FnOnce() + 'static
+  ) -> CartonsAnimation {
+
+    let frames = Rc::new(Frames {
+      lateral,
+      elems: elems.into_iter().map(|x| x.as_ref().clone()).collect(),
+      from, to,
+      transition: *self,
+      start: Cell::new(None),
+      frame: Cell::new(None),
+      cb: RefCell::new(None),
+      done: RefCell::new(Some(Box::new(done))),
+    });
+
+    if self.duration<=0. || Self::reduced_motion() {
+      frames.end(true);
+      return CartonsAnimation(frames);
+    }
+
+    frames.set_style(0.);
+
+    let frames_ = frames.clone();
+    *frames.cb.borrow_mut() = Some(Closure::<dyn FnMut(_)>::new(move |now: f64| {
+      let start = frames_.start.get().unwrap_or(now);
+      frames_.start.set(Some(start));
+      let t = (now-start)/frames_.transition.duration;
+      if t>=1. {
+        frames_.end(true);
+      } else {
+        frames_.set_style(frames_.transition.easing.ease(t));
+        frames_.request();
+      }
+    }));
+    frames.request();
+
+    CartonsAnimation(frames)
+  }
+}
+
+/// `requestAnimationFrame` callback, holding itself until the animation ends
+type FrameCallback = RefCell<Option<Closure<dyn FnMut(f64)>>>;
+
+/// States of an animation
+struct Frames {
+  lateral: bool,
+  elems: Vec<HtmlElement>,
+  from: Vec<f64>,
+  to: Vec<f64>,
+  transition: CartonsTransition,
+  start: Cell<Option<f64>>,
+  frame: Cell<Option<i32>>,
+  cb: FrameCallback,
+  done: RefCell<Option<Box<dyn FnOnce()>>>,
+}
+
+impl Frames {
+
+  /// Same style with `CartonsComplex`'s own update, at (eased) progress `t`
+  fn set_style(&self, t: f64) {
+    set_cartons_style(self.lateral, self.elems.iter().collect(), CartonsTransition::interpolate(&self.from, &self.to, t).into_iter(), 0);
+  }
+
+  fn request(&self) {
+    if let Some(cb) = self.cb.borrow().as_ref() {
+      self.frame.set(gloo_utils::window().request_animation_frame(cb.as_ref().unchecked_ref()).ok());
+    }
+  }
+
+  /// Stop frames. When `finish`, set the end style and call `done`.
+  fn end(&self, finish: bool) {
+    if let Some(frame) = self.frame.take() {
+      let _ = gloo_utils::window().cancel_animation_frame(frame);
+    }
+    // breaks the reference cycle of the closure
+    let _ = self.cb.borrow_mut().take();
+    if let Some(done) = self.done.borrow_mut().take() {
+      if finish {
+        self.set_style(1.);
+        done();
+      }
+    }
+  }
+}
+
+/// Handle of a running animation. Dropping it doesn't stop the animation.
+pub struct CartonsAnimation(Rc<Frames>);
+
+impl CartonsAnimation {
+
+  /// Is the animation running?
+  pub fn running(&self) -> bool {
+    self.0.done.borrow().is_some()
+  }
+
+  /// Jump to the end, emitting the final state
+  pub fn finish(&self) {
+    self.0.end(true);
+  }
+
+  /// Stop where it is, without emitting the final state. Ex. when drag-resizing starts.
+  pub fn cancel(&self) {
+    self.0.end(false);
+  }
+}
+
+/// Slot of the latest animation of a resizer, to be stopped when another work starts
+#[derive(Default)]
+pub struct AnimationSlot(RefCell<Option<CartonsAnimation>>);
+
+impl AnimationSlot {
+
+  /// Keep a new animation, finishing the former one. (Check [`finish()`](Self::finish))
+  pub fn replace(&self, animation: Option<CartonsAnimation>) {
+    self.finish();
+    *self.0.borrow_mut() = animation;
+  }
+
+  /// Jump to the end, emitting the final state. Ex. before a new work, so that it starts from the final state.
+  pub fn finish(&self) {
+    // taken out first, as emitting the final state may reach the slot again
+    let animation = self.0.borrow_mut().take();
+    if let Some(animation) = animation {
+      animation.finish();
+    }
+  }
+
+  /// Stop where it is, without emitting the final state. Ex. when drag-resizing may start.
+  pub fn cancel(&self) {
+    let animation = self.0.borrow_mut().take();
+    if let Some(animation) = animation {
+      animation.cancel();
+    }
+  }
+}
+
+
+impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
+
+  /// Animate style changes of a work.
+  ///
+  /// Cartons' sizes are measured before and after the `work`, which updates their style as usual.
+  /// Then the transition animates between them, and the `work`'s output is passed to `done` when it completes.
+  pub fn transit<E: AsRef<Element>, R: 'static>(
+    &self,
+    wrap: E,
+    transition: CartonsTransition,
+    work: impl FnOnce(&Element) -> Result<R>,
+    done: impl FnOnce(R) + 'static
+  ) -> Result<CartonsAnimation> {
+
+    let wrap = wrap.as_ref();
+    let (elems, _) = Self::wrap_to_carton_elems(wrap, self.name);
+    let measure = |elems: &Vec<HtmlElement>| -> Vec<f64> {
+      elems.iter().map(|x| get_box_size(x, self.lateral, self.carton_box)).collect()
+    };
+
+    let from = measure(&elems);
+    let output = work(wrap)?;
+    let to = measure(&elems);
+
+    Ok(transition.animate(self.lateral, elems, from, to, move || done(output)))
+  }
+
+  /// Run a discrete resizing work, animated with the complex's field `transition` if any.
+  /// The work's output is passed to `done` at once without transition, or when the animation completes.
+  ///
+  /// Return the animation, if animated. Keep it to stop the animation, like by an [`AnimationSlot`].
+  pub fn with_transition(
+    &self,
+    wrap: &Element,
+    work: impl FnOnce(&Element) -> Result<ResizeOutput<T>>,
+    done: impl FnOnce(ResizeOutput<T>) + 'static
+  ) -> Result<Option<CartonsAnimation>> where T: 'static {
+    match self.transition {
+      Some(transition) => self.transit(wrap, transition, work, done).map(Some),
+      None => work(wrap).map(done).map(|_| None),
+    }
+  }
+
+  /// Animated [`wrap_effect_on_update()`](Self::wrap_effect_on_update). New metric is passed to `done` when it completes.
+  ///
+  /// Mind that it's for programmatic changes, like adding or removing cartons, not for continuous ones like window resizing.
+  pub fn animated_wrap_effect_on_update<X: Copy + 'static, E: AsRef<Element>>(
+    &self,
+    wrap: X,
+    get_elem: impl Fn(X) -> Option<E> + Copy + 'static,
+    transition: CartonsTransition,
+    done: impl FnOnce(CartonsMetric<T>) + 'static
+  ) -> Result<CartonsAnimation> where T: 'static {
+
+    let Some(wrap) = get_elem(wrap) else { return Err(Error::Ignore) };
+    self.transit(wrap, transition, |wrap| Ok(self.wrap_effect(wrap)), done)
+  }
+
+  /// Animated [`switch_zero()`](Self::switch_zero).
+  /// Outputs like [`resize_work()`](Self::resize_work) are passed to `done` when it completes:
+  /// apply them by [`apply_resized()`](Self::apply_resized).
+  pub fn animated_switch_zero<E: AsRef<Element>>(
+    &self,
+    wrap: E,
+    data: &T,
+    on: bool,
+    transition: CartonsTransition,
+    done: impl FnOnce(ResizeOutput<T>) + 'static
+  ) -> Result<CartonsAnimation> where T: 'static {
+    self.transit(wrap, transition, |wrap| self.switched_zero(wrap, data, on), done)
+  }
+
+  /// Expand [`animated_wrap_effect_on_update()`](Self::animated_wrap_effect_on_update) for Sycamore.
+  /// The signal's metric is updated when the animation completes.
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn animate_wrap<G: GenericNode>(
+    complex: Signal<Self>,
+    wrap_ref: NodeRef<G>,
+    transition: CartonsTransition
+  ) -> Result<CartonsAnimation> where T: 'static {
+    complex.with_untracked(|x| x.animated_wrap_effect_on_update(wrap_ref, ref_get::<_, Element>, transition, move |metric| {
+      complex.update(|complex| complex.metric = metric);
+    }))
+  }
+
+  /// Expand [`animated_switch_zero()`](Self::animated_switch_zero) for Sycamore.
+  /// The signal is updated when the animation completes.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// # use sycamore::prelude::*;
+  /// #[component(inline_props)]
+  /// fn Sidebar<G: Html>(complex: Signal<CartonsComplex<usize>>, wrap_ref: NodeRef<G>) -> View<G> {
+  ///   let collapsed = create_signal(false);
+  ///   let toggle = move |_| {
+  ///     let transition = CartonsTransition::new(250., Easing::EaseOut);
+  ///     if CartonsComplex::animate_switch_zero(complex, wrap_ref, 0, collapsed.get(), transition).is_ok() {
+  ///       collapsed.set(!collapsed.get());
+  ///     }
+  ///   };
+  ///
+  ///   view! {
+  ///     button(on:click=toggle) { "toggle sidebar" }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn animate_switch_zero<G: GenericNode>(
+    complex: Signal<Self>,
+    wrap_ref: NodeRef<G>,
+    data: T,
+    on: bool,
+    transition: CartonsTransition
+  ) -> Result<CartonsAnimation> where T: 'static {
+    let Some(wrap) = ref_get::<_, Element>(wrap_ref) else { return Err(Error::Ignore) };
+    complex.with_untracked(|x| x.animated_switch_zero(wrap, &data, on, transition, move |(metric, zeroed_cache, zero_restored)| {
+      complex.update(|complex| complex.apply_resized(metric, zeroed_cache, zero_restored));
+    }))
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Animation of no elements, whose `done` writes the metric
+  fn animation(metric: &Rc<RefCell<&'static str>>, value: &'static str) -> CartonsAnimation {
+    let metric = metric.clone();
+    CartonsAnimation(Rc::new(Frames {
+      lateral: true,
+      elems: vec![],
+      from: vec![],
+      to: vec![],
+      transition: CartonsTransition::default(),
+      start: Cell::new(None),
+      frame: Cell::new(None),
+      cb: RefCell::new(None),
+      done: RefCell::new(Some(Box::new(move || *metric.borrow_mut() = value))),
+    }))
+  }
+
+  #[test]
+  fn cancel_then_drag() {
+    let metric = Rc::new(RefCell::new("initial"));
+    let slot = AnimationSlot::default();
+    slot.replace(Some(animation(&metric, "toggled")));
+
+    // pointerdown, then drag-resizing applies its output
+    slot.cancel();
+    *metric.borrow_mut() = "dragged";
+
+    // the cancelled animation never overwrites the drag
+    slot.finish();
+    slot.replace(None);
+    assert_eq!(*metric.borrow(), "dragged");
+  }
+
+  #[test]
+  fn new_work_finishes_former() {
+    let metric = Rc::new(RefCell::new("initial"));
+    let slot = AnimationSlot::default();
+    slot.replace(Some(animation(&metric, "former")));
+    slot.replace(Some(animation(&metric, "latter")));
+    assert_eq!(*metric.borrow(), "former");
+
+    slot.finish();
+    assert_eq!(*metric.borrow(), "latter");
+  }
+}
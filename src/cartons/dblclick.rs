@@ -0,0 +1,108 @@
+//! Double-click actions of resizers
+//!
+//! Desktop split panes and table headers react to double-click on their resizers.
+//! Set [`CartonsComplex`]'s field `dblclick` with a [`DblClickAction`]:
+//! * `ToggleZero`: collapse the carton, or restore it. (Same with [`CartonsComplex::switch_zero()`])
+//! * `Reset`: reset all cartons to the complex's `initial_metric`, clearing their zeroed cache.
+//! * `AutoFit`: fit the carton, which the resizer trails, to its content's intrinsic size, limited by its min/max.
+//!
+//! `CartonsComplex::init_resizer()` listens to `dblclick` of resizers with its pointer and keyboard listeners. (*feature `sycamore`*)
+//! Its drag-resizing starts only after the pointer moves past a few pixels, so that clicks of a double-click don't resize.
+//! Actions are animated by the complex's `transition`, if any. (Check [`CartonsTransition`])
+//! The generalized one is [`CartonsComplex::dblclick_work()`].
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let mut complex = CartonsComplex::<usize>::new(
+//!   true, false, None,
+//!   vec![(0, Some(Sizon::rel(0.3))), (1, Some(Sizon::rel(0.7)))].into(),
+//!   (vec![], Sizon::abs(100.)).into(), (vec![(0, Sizon::rel(0.5))], Sizon::rel(1.)).into(),
+//!   vec![].into(), vec![].into(), vec![].into()
+//! );
+//! complex.dblclick = Some(DblClickAction::AutoFit);
+//!
+//! // content's intrinsic sizes, in a wrap of 1000px
+//! assert_eq!(complex.auto_fit_size(&0, 240., 1000.), 240.);
+//! assert_eq!(complex.auto_fit_size(&0, 40., 1000.), 100.);
+//! assert_eq!(complex.auto_fit_size(&0, 720., 1000.), 500.);
+//! ```
+
+use super::*;
+
+/// Action of a resizer's double-click
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DblClickAction {
+  /// toggle zero state of the carton
+  ToggleZero,
+  /// reset to the initial metric
+  Reset,
+  /// fit the carton to its content's intrinsic size
+  AutoFit,
+}
+
+/// Measure an element's intrinsic size, with `max-content` size style for a moment.
+fn intrinsic_size(elem: &HtmlElement, lateral: bool, measure: MeasureBox) -> f64 {
+  let (size_prop, _) = size_pos_props(lateral);
+  let style = elem.style();
+  let former = style.get_property_value(size_prop).unwrap_or_default();
+
+  let _ = style.set_property(size_prop, "max-content");
+  let size = get_box_size(elem, lateral, measure);
+  let _ = style.set_property(size_prop, &former);
+  size
+}
+
+impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
+
+  /// Size of the carton to be auto-fitted: the content's size limited by the carton's min/max.
+  pub fn auto_fit_size(&self, data: &T, content_size: f64, wrap_size: f64) -> f64 {
+    self.limited(data, content_size, wrap_size)
+  }
+
+  /// Conduct the `dblclick` action on the resizer of carton `data`.
+  /// Return `Error::Ignore` when there is no action or nothing changes.
+  ///
+  /// Outputs are same with [`resize_work()`](Self::resize_work): apply them by [`apply_resized()`](Self::apply_resized).
+  pub fn dblclick_work<X: Copy + 'static, E: AsRef<Element>>(
+    &self,
+    data: &T,
+    wrap: X,
+    get_elem: impl Fn(X) -> Option<E> + Copy + 'static,
+  ) -> Result<ResizeOutput<T>> {
+
+    let Some(wrap) = get_elem(wrap) else { return Err(Error::Ignore) };
+    self.dblclick_on(wrap.as_ref(), data)
+  }
+
+  /// [`dblclick_work()`](Self::dblclick_work) on the wrap element
+  pub(super) fn dblclick_on(&self, wrap: &Element, data: &T) -> Result<ResizeOutput<T>> {
+
+    let Some(action) = self.dblclick else { return Err(Error::Ignore) };
+
+    match action {
+      DblClickAction::ToggleZero => self.toggled_zero(wrap, data),
+
+      DblClickAction::Reset => {
+        let mut complex = self.clone();
+        complex.metric = self.initial_metric.clone();
+        // zeroed cache belongs to the current metric: clear all of them
+        let zero_restored = self.zeroed_cache.map.keys().cloned().collect();
+        Ok((complex.wrap_effect(wrap), HashMap::new(), zero_restored))
+      },
+
+      DblClickAction::AutoFit => {
+        let (wrap_size, elems, _, index, size) = self.measures(wrap, data)?;
+        let content_size = intrinsic_size(&elems[index], self.lateral, self.carton_box);
+        let delta = self.auto_fit_size(data, content_size, wrap_size) - size.unwrap_or(0.);
+
+        let mut cache = vec![];
+        let mut zeroed_cache = HashMap::new();
+        let mut zero_restored = HashSet::new();
+
+        let metric = self.update_resize(wrap, data, delta, &mut cache, &mut zeroed_cache, &mut zero_restored)?;
+        Ok((metric, zeroed_cache, zero_restored))
+      },
+    }
+  }
+}
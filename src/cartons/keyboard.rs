@@ -0,0 +1,147 @@
+//! Keyboard-operable resizers with ARIA separator semantics
+//!
+//! A resizer of a carton moves the carton's rear edge. Focused, it's resized by keys:
+//! * Arrow keys along the complex's direction(`ArrowLeft`/`ArrowRight` for lateral, `ArrowUp`/`ArrowDown` for vertical):
+//!   shrink or expand the carton by [`KeyStep`]'s `step`, or `large` step with `Shift`.
+//! * `Home`/`End`: resize the carton to its min/max limit.
+//! * `Enter`: toggle its zero state with [`CartonsComplex::switch_zero()`]. It's animated by the complex's `transition`, if any.
+//!
+//! Resizing goes through the same dependent/independent resizing logic with pointers. => [`CartonsComplex::key_resize_work()`]
+//!
+//! A resizer is a [`separator`](https://www.w3.org/TR/wai-aria-1.2/#separator) for assistive technologies.
+//! [`CartonsComplex::set_resizer_aria()`] sets its `role`, `aria-orientation` and `aria-valuenow/min/max`.
+//! Values are percentages of the complex's total size.
+//!
+//! `CartonsComplex::init_resizer()` does them all for Sycamore. (*feature `sycamore`*)
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let step = KeyStep::default();
+//! assert_eq!(step.delta(true, "ArrowRight", false, 200., (100., 400.)), Some(10.));
+//! assert_eq!(step.delta(true, "ArrowLeft", true, 200., (100., 400.)), Some(-50.));
+//! assert_eq!(step.delta(true, "ArrowDown", false, 200., (100., 400.)), None);
+//! assert_eq!(step.delta(false, "End", false, 200., (100., 400.)), Some(200.));
+//!
+//! let mut complex = CartonsComplex::<usize>::new(
+//!   true, false, None,
+//!   vec![(0, Some(Sizon::abs(300.))), (1, Some(Sizon::abs(700.)))].into(),
+//!   (vec![], Sizon::abs(100.)).into(), (vec![], Sizon::rel(0.5)).into(),
+//!   vec![].into(), vec![].into(), vec![].into()
+//! );
+//! complex.metric.default = Some(Sizon::abs(1000.)); // total size
+//! assert_eq!(complex.aria_values(&0), (30., 10., 50.));
+//! ```
+
+use super::*;
+use web_sys::KeyboardEvent;
+
+/// Steps of keyboard resizing in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct KeyStep {
+  /// step of arrow keys
+  pub step: f64,
+  /// step of arrow keys with `Shift`
+  pub large: f64,
+}
+
+impl Default for KeyStep {
+  fn default() -> Self {
+    Self { step: 10., large: 50. }
+  }
+}
+
+impl KeyStep {
+
+  pub fn new(step: f64, large: f64) -> Self {
+    Self { step, large }
+  }
+
+  /// Delta of a carton's size by the key. Return None when the key is not for resizing.
+  ///
+  /// # Args
+  /// * lateral: direction of the complex
+  /// * key, shift: `key` and `shiftKey` of the keyboard event
+  /// * size: current size of the carton
+  /// * (min, max): size limits of the carton
+  pub fn delta(&self, lateral: bool, key: &str, shift: bool, size: f64, (min, max): (f64, f64)) -> Option<f64> {
+    let step = if shift { self.large } else { self.step };
+    let (backward, forward) = if lateral { ("ArrowLeft", "ArrowRight") } else { ("ArrowUp", "ArrowDown") };
+
+    match key {
+      x if x==forward => Some(step),
+      x if x==backward => Some(-step),
+      "Home" => Some(min-size),
+      "End" => Some(max-size),
+      _ => None,
+    }
+  }
+}
+
+
+impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
+
+  /// Conduct resizing job of a resizer by a keydown event. Check the [module](crate::cartons::keyboard) doc about keys.
+  ///
+  /// Outputs are same with [`resize_work()`](Self::resize_work): apply them by [`apply_resized()`](Self::apply_resized).
+  pub fn key_resize_work<X: Copy + 'static, E: AsRef<Element>>(
+    &self,
+    e: &KeyboardEvent,
+    data: &T,
+    wrap: X,
+    get_elem: impl Fn(X) -> Option<E> + Copy + 'static,
+  ) -> Result<ResizeOutput<T>> {
+
+    let Some(wrap) = get_elem(wrap) else { return Err(Error::Ignore) };
+    let key = e.key();
+
+    if key=="Enter" {
+      return self.toggled_zero(&wrap, data);
+    }
+
+    let (wrap_size, _, _, _, size) = self.measures(&wrap, data)?;
+    let min = self._min(data, wrap_size);
+    let max = self._max(data, wrap_size).unwrap_or(wrap_size);
+    let Some(delta) = self.key_step.delta(self.lateral, &key, e.shift_key(), size.unwrap_or(0.), (min, max)) else { return Err(Error::Ignore) };
+
+    let mut cache = vec![];
+    let mut zeroed_cache = HashMap::new();
+    let mut zero_restored = HashSet::new();
+
+    let metric = self.update_resize(&wrap, data, delta, &mut cache, &mut zeroed_cache, &mut zero_restored)?;
+    Ok((metric, zeroed_cache, zero_restored))
+  }
+
+  /// Values of a resizer's `aria-valuenow/min/max`: the carton's size and limits, in percentage of the total size.
+  /// A zeroed carton's size is 0.
+  pub fn aria_values(&self, data: &T) -> (f64, f64, f64) {
+    let total = self.metric.default.and_then(|x| x.abs).unwrap_or_default();
+    if total<=0. {
+      return (0., 0., 100.);
+    }
+    let size = self.metric.map.get(data).copied().flatten().and_then(|x| x.abs).unwrap_or_default();
+    let min = self._min(data, total).min(total);
+    let max = self._max(data, total).unwrap_or(total).min(total);
+
+    let percent = |x: f64| x/total*100.;
+    (percent(size), percent(min), percent(max))
+  }
+
+  /// Set ARIA attributes of the resizer of carton `data`: `role="separator"`, `aria-orientation` and `aria-valuenow/min/max`.
+  /// The resizer gets `tabindex="0"` to be focusable, if it has no tabindex.
+  pub fn set_resizer_aria<E: AsRef<Element>>(&self, resizer: E, data: &T) {
+    let resizer = resizer.as_ref();
+    let (now, min, max) = self.aria_values(data);
+    // separator between lateral cartons is vertical
+    let orientation = if self.lateral { "vertical" } else { "horizontal" };
+
+    let _ = resizer.set_attribute("role", "separator");
+    let _ = resizer.set_attribute("aria-orientation", orientation);
+    let _ = resizer.set_attribute("aria-valuenow", &format!("{:.0}", now));
+    let _ = resizer.set_attribute("aria-valuemin", &format!("{:.0}", min));
+    let _ = resizer.set_attribute("aria-valuemax", &format!("{:.0}", max));
+    if !resizer.has_attribute("tabindex") {
+      let _ = resizer.set_attribute("tabindex", "0");
+    }
+  }
+}
@@ -0,0 +1,250 @@
+//! Persist and restore cartons' layout state
+//!
+//! Users expect their panel sizes back after reload.
+//! [`CartonsState`] is a serializable and versioned state of a [`CartonsComplex`]: sizes(`metric`) and `zeroed_cache`.
+//! Rule maps(`min`, `max`, `allow_zero`, `zeroed_when`), `name` and the other settings stay in code,
+//! so that changed rules take effect even with states saved before. (They're serializable on their own, as [`CartonsMap`]s)
+//!
+//! * [`CartonsComplex::state()`] and [`CartonsComplex::restore()`] convert between them.
+//! * [`CartonsComplex::save()`] and [`CartonsComplex::load()`] write and read the state as JSON, with a [`CartonsStorage`].
+//! * [`LocalStorage`] is the browser's `localStorage`, and [`MemoryStorage`] keeps states in memory.
+//!   Implement `CartonsStorage` for others, like a server or IndexedDB cache.
+//! * `CartonsComplex::init_persist()` loads and saves a complex signal's state automatically,
+//!   when resizing ends and only if the state has changed. (*feature `sycamore`*)
+//!
+//! Cartons may be added or removed since the state was saved.
+//! Restoring keeps current entries and `default` values, then overwrites entries with saved ones:
+//! * Added cartons get their current values, or the default.
+//! * Entries of removed cartons are left unused. Metric's ones are dropped at next wrap effect.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let new_complex = || CartonsComplex::<usize>::new(
+//!   true, false, None,
+//!   vec![(0, Some(Sizon::rel(0.3))), (1, Some(Sizon::rel(0.7)))].into(),
+//!   vec![].into(), vec![].into(), vec![(0, true)].into(), vec![].into(), vec![].into()
+//! );
+//!
+//! let storage = MemoryStorage::default();
+//!
+//! let mut complex = new_complex();
+//! complex.metric.insert(0, None); // zeroed
+//! complex.zeroed_cache.insert(0, 0.3);
+//! complex.save(&storage, "panels").unwrap();
+//!
+//! // after reload, with a new carton
+//! let mut complex = new_complex();
+//! complex.metric.insert(2, Some(Sizon::rel(0.2)));
+//! complex.allow_zero.insert(1, true); // rules set in code are kept
+//! complex.load(&storage, "panels").unwrap();
+//! assert!(*complex.allow_zero.get(&1));
+//! assert_eq!(*complex.metric.get(&0), None);
+//! assert_eq!(*complex.metric.get(&1), Some(Sizon::rel(0.7)));
+//! assert_eq!(*complex.metric.get(&2), Some(Sizon::rel(0.2)));
+//! assert_eq!(*complex.zeroed_cache.get(&0), 0.3);
+//!
+//! assert!(matches!(complex.load(&storage, "unknown"), Err(webtric::error::Error::Ignore)));
+//!
+//! // states of version 1 had rule maps, which are not restored
+//! let v1 = r#"{"version": 1,
+//!   "metric": {"map": {"1": {"abs": null, "rel": 0.5}}, "default": null},
+//!   "zeroed_cache": {"map": {}, "default": 0.0},
+//!   "allow_zero": {"map": {"1": false}, "default": false}
+//! }"#;
+//! storage.set_item("panels", v1).unwrap();
+//! complex.load(&storage, "panels").unwrap();
+//! assert_eq!(*complex.metric.get(&1), Some(Sizon::rel(0.5)));
+//! assert!(*complex.allow_zero.get(&1));
+//! ```
+
+use super::*;
+use std::cell::RefCell;
+use serde::de::DeserializeOwned;
+
+/// Serializable state of a [`CartonsComplex`]. Check the [module](crate::cartons::persist) doc.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CartonsState<T: Eq + Hash + FromStr + Clone> {
+  /// version of the state's format. States of newer versions are not restored.
+  pub version: u32,
+  pub metric: CartonsMetric<T>,
+  pub zeroed_cache: CartonsMap<T, f64>,
+}
+
+impl<T: Eq + Hash + FromStr + Clone> CartonsState<T> {
+  /// current version of the state's format
+  /// * 1: with rule maps, which are ignored now.
+  /// * 2: sizes and zeroed cache only
+  pub const VERSION: u32 = 2;
+}
+
+impl<T: Eq + Hash + FromStr + Clone, V> CartonsMap<T, V> {
+
+  /// Overwrite entries with `other`'s ones, keeping other entries and `default`.
+  pub fn merge(&mut self, other: CartonsMap<T, V>) {
+    self.map.extend(other.map);
+  }
+}
+
+
+/// Storage of serialized states, by keys
+pub trait CartonsStorage {
+  /// Get the stored value of the key, if any
+  fn get_item(&self, key: &str) -> Option<String>;
+  /// Store the value with the key
+  fn set_item(&self, key: &str, value: &str) -> Result<()>;
+}
+
+/// Browser's `localStorage`
+#[derive(Default, Debug, Clone, Copy)]
+pub struct LocalStorage;
+
+impl LocalStorage {
+  fn storage() -> Result<web_sys::Storage> {
+    gloo_utils::window().local_storage().ok().flatten()
+      .ok_or_else(|| Error::Msg(String::from("localStorage is not available")))
+  }
+}
+
+impl CartonsStorage for LocalStorage {
+  fn get_item(&self, key: &str) -> Option<String> {
+    Self::storage().ok()?.get_item(key).ok().flatten()
+  }
+
+  fn set_item(&self, key: &str, value: &str) -> Result<()> {
+    Self::storage()?.set_item(key, value)
+      .map_err(|e| Error::Msg(format!("failed to set localStorage: {:?}", e)))
+  }
+}
+
+/// In-memory storage. Ex. for server side rendering or tests
+#[derive(Default, Debug, Clone)]
+pub struct MemoryStorage(pub RefCell<HashMap<String, String>>);
+
+impl CartonsStorage for MemoryStorage {
+  fn get_item(&self, key: &str) -> Option<String> {
+    self.0.borrow().get(key).cloned()
+  }
+
+  fn set_item(&self, key: &str, value: &str) -> Result<()> {
+    self.0.borrow_mut().insert(key.to_string(), value.to_string());
+    Ok(())
+  }
+}
+
+
+impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
+
+  /// Current state to be persisted
+  pub fn state(&self) -> CartonsState<T> {
+    CartonsState {
+      version: CartonsState::<T>::VERSION,
+      metric: self.metric.clone(),
+      zeroed_cache: self.zeroed_cache.clone(),
+    }
+  }
+
+  /// Restore a persisted state. Return error when its version is unknown.
+  ///
+  /// Saved entries overwrite current ones, while cartons not in the state keep theirs.
+  /// Rule maps are not touched.
+  pub fn restore(&mut self, state: CartonsState<T>) -> Result<()> {
+    if !(1..=CartonsState::<T>::VERSION).contains(&state.version) {
+      return Err(Error::Msg(format!("unsupported cartons state version: {}", state.version)));
+    }
+    self.metric.merge(state.metric);
+    self.zeroed_cache.merge(state.zeroed_cache);
+    Ok(())
+  }
+}
+
+impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug + Serialize> CartonsComplex<T> {
+
+  /// Current state as JSON
+  pub fn state_json(&self) -> Result<String> {
+    serde_json::to_string(&self.state()).map_err(|e| Error::Msg(e.to_string()))
+  }
+
+  /// Save current state into the storage as JSON
+  pub fn save<S: CartonsStorage>(&self, storage: &S, key: &str) -> Result<()> {
+    storage.set_item(key, &self.state_json()?)
+  }
+}
+
+impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug + DeserializeOwned> CartonsComplex<T> {
+
+  /// Load a saved state from the storage and restore it.
+  /// Return `Error::Ignore` when nothing is saved with the key.
+  pub fn load<S: CartonsStorage>(&mut self, storage: &S, key: &str) -> Result<()> {
+    let Some(value) = storage.get_item(key) else { return Err(Error::Ignore) };
+    let state: CartonsState<T> = serde_json::from_str(&value).map_err(|e| Error::Msg(e.to_string()))?;
+    self.restore(state)
+  }
+}
+
+impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug + Serialize + DeserializeOwned + 'static> CartonsComplex<T> {
+
+  /// Persist a complex signal's state in the storage, for Sycamore.
+  ///
+  /// The saved state is loaded at once, so call it before [`init_wrap()`](Self::init_wrap) makes its effect.
+  /// Then the state is saved when the signal changes, but not while `resizing`: pass the returned signal to resizers,
+  /// so that it's saved once when resizing ends. Unchanged states are not written again.
+  ///
+  /// # Args
+  /// * complex: the signal of CartonsComplex
+  /// * storage, key: where to save the state
+  /// * resizing: resizing signal shared with resizers. (See [`init_resizer()`](Self::init_resizer))
+  ///
+  /// # Outputs
+  /// * resizing
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// # use sycamore::prelude::*;
+  /// #[component(inline_props)]
+  /// fn Panels<G: Html>(complex: Signal<CartonsComplex<usize>>) -> View<G> {
+  ///   let resizing = CartonsComplex::init_persist(complex, LocalStorage, "panels", None);
+  ///   let WindowResizing(window_resizing) = use_context();
+  ///   let wrap_ref = CartonsComplex::init_wrap(complex, None, window_resizing);
+  ///   let (resizer_ref, _) = CartonsComplex::init_resizer(complex, wrap_ref, None, 0, Some(resizing));
+  ///
+  ///   view! {
+  ///     div(ref=wrap_ref, style="position: relative;") {
+  ///       div(data-carton="0") { "left" div(ref=resizer_ref) }
+  ///       div(data-carton="1") { "right" }
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn init_persist<S: CartonsStorage + 'static>(
+    complex: Signal<Self>,
+    storage: S,
+    key: &'static str,
+    resizing: Option<Signal<bool>>
+  ) -> Signal<bool> {
+
+    let resizing = resizing.unwrap_or(create_signal(false));
+
+    let saved = complex.update_silent(|complex| {
+      let _ = complex.load(&storage, key);
+      complex.state_json().ok()
+    });
+    let saved = std::rc::Rc::new(RefCell::new(saved));
+
+    create_effect(on((complex, resizing), move || {
+      if resizing.get_untracked() {
+        return;
+      }
+      let Ok(value) = complex.with_untracked(|complex| complex.state_json()) else { return };
+      if saved.borrow().as_ref()!=Some(&value) && storage.set_item(key, &value).is_ok() {
+        *saved.borrow_mut() = Some(value);
+      }
+    }));
+
+    resizing
+  }
+}
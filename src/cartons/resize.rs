@@ -8,6 +8,16 @@
 
 use super::*;
 use std::cmp::Ordering;
+#[cfg(feature="sycamore")]
+use std::{cell::Cell, rc::Rc};
+
+/// Distance in pixels which the pointer moves from pointerdown, before drag-resizing starts.
+/// Clicks of a double-click don't resize within it.
+pub const DRAG_THRESHOLD: f64 = 3.;
+
+/// Access to a complex inside of a signal or a tree
+#[cfg(feature="sycamore")]
+type ComplexWith<T> = dyn Fn(&mut dyn FnMut(&CartonsComplex<T>));
 
 impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
 
@@ -342,7 +352,7 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
   
   /// Update sizing state,
   /// from a resizer with given `data` ...
-  fn update_resize<E: AsRef<Element>>(
+  pub(super) fn update_resize<E: AsRef<Element>>(
     &self,
     wrap: E,
     data: &T,
@@ -475,6 +485,19 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
     Ok(())
   }
 
+  /// Toggle zero state with [`switch_zero()`](Self::switch_zero) on a clone, and return outputs like resizing works.
+  pub(super) fn toggled_zero<E: AsRef<Element>>(&self, wrap: E, data: &T) -> Result<ResizeOutput<T>> {
+    self.switched_zero(wrap, data, self._zeroed(data))
+  }
+
+  /// Switch zero state with [`switch_zero()`](Self::switch_zero) on a clone, and return outputs like resizing works.
+  pub(super) fn switched_zero<E: AsRef<Element>>(&self, wrap: E, data: &T, on: bool) -> Result<ResizeOutput<T>> {
+    let mut complex = self.clone();
+    complex.switch_zero(wrap, data, on)?;
+    let zero_restored = self.zeroed_cache.map.keys().filter(|x| !complex.zeroed_cache.map.contains_key(*x)).cloned().collect();
+    Ok((complex.metric, complex.zeroed_cache.map, zero_restored))
+  }
+
   /// Conduct resizing job of a resizer, which is attached to each carton and manually resizes with pointerdown/move event.
   pub fn resize_work<X: Copy + 'static, E: AsRef<Element>>(
     &self,
@@ -486,7 +509,7 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
     wrap: X,
     resizer: X,
     get_elem: impl Fn(X) -> Option<E> + Copy + 'static,
-  ) -> Result<(CartonsMetric<T>, HashMap<T, f64>, HashSet<T>)> {
+  ) -> Result<ResizeOutput<T>> {
 
     let Some(wrap) = get_elem(wrap) else { return Err(Error::Ignore) };
     let Some(resizer) = get_elem(resizer) else { return Err(Error::Ignore) };
@@ -514,9 +537,28 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
     Ok((metric, zeroed_cache, zero_restored))
   }
 
+  /// Apply outputs of [`resize_work()`] to the complex: new metric and changes of zeroed cache.
+  pub fn apply_resized(&mut self, metric: CartonsMetric<T>, zeroed_cache: HashMap<T, f64>, zero_restored: HashSet<T>) {
+    self.metric = metric;
+    for x in zero_restored.iter() {
+      self.zeroed_cache.remove(x);
+    }
+    for (k, v) in zeroed_cache.into_iter() {
+      self.zeroed_cache.insert(k, v);
+    }
+  }
+
   /// Expand [`resize_work()`] for Sycamore.
   /// Initialte a resizer handler, which is attached to each carton and manually resizes with pointerdown/move event.
   /// 
+  /// The resizer is also keyboard-operable with keydown event, and has ARIA separator attributes in sync with the metric.
+  /// Check [`key_resize_work()`](Self::key_resize_work) and [`set_resizer_aria()`](Self::set_resizer_aria).
+  /// Double-click does the complex's `dblclick` action. Check [`dblclick_work()`](Self::dblclick_work).
+  /// 
+  /// Thus drag-resizing starts only after the pointer moves [`DRAG_THRESHOLD`] pixels.
+  /// `resizing` is set `true` then, not at pointerdown: a plain click or double-click never turns it on.
+  /// It's set `false` at pointerup, only when it was on.
+  /// 
   /// *feature `sycamore`*
   #[cfg(feature="sycamore")]
   pub fn init_resizer<G: GenericNode>(
@@ -529,6 +571,28 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
     NodeRef<G>,
     Signal<bool>
   ) {
+    Self::init_resizer_with(
+      move |work| complex.with(|complex| work(complex)),
+      move |(metric, zeroed_cache, zero_restored)| complex.update(|complex| complex.apply_resized(metric, zeroed_cache, zero_restored)),
+      wrap_ref, resizer_ref, data, resizing
+    )
+  }
+
+  /// Inner work of [`init_resizer()`], getting the complex by `with` and applying outputs of [`resize_work()`] by `apply`.
+  /// 
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub(crate) fn init_resizer_with<G: GenericNode>(
+    with: impl Fn(&mut dyn FnMut(&Self)) + Clone + 'static,
+    apply: impl Fn(ResizeOutput<T>) + Clone + 'static,
+    wrap_ref: NodeRef<G>,
+    resizer_ref: Option<NodeRef<G>>,
+    data: T,
+    resizing: Option<Signal<bool>>
+  ) -> (
+    NodeRef<G>,
+    Signal<bool>
+  ) where T: 'static {
 
     let resizer_ref = resizer_ref.unwrap_or(create_node_ref());
     let resizing = resizing.unwrap_or(create_signal(false));
@@ -536,56 +600,121 @@ impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsComplex<T> {
     let pos: *mut Option<f64> = Box::into_raw(Box::new(None));
     let shift: *mut Option<f64> = Box::into_raw(Box::new(None));
     let cache: *mut Vec<(usize, f64)> = Box::into_raw(Box::new(vec![]));
+    // pointer position at pointerdown, until the drag starts
+    let pressed: Rc<Cell<Option<f64>>> = Rc::new(Cell::new(None));
+    // animation of `Enter` key or double-click
+    let animation = Rc::new(AnimationSlot::default());
 
+    let (with_, data_, pressed_) = (with.clone(), data.clone(), pressed.clone());
+    let apply_ = apply.clone();
     let pointer_move = move |e: PointerEvent| {
 
-      if let Ok((metric, zeroed_cache, zero_restored)) = 
-        complex.with(|complex| complex.resize_work(e, &data, pos, shift, cache, wrap_ref, resizer_ref, ref_get::<_, Element>))
-      {  
-        complex.update(|complex| {
-          complex.metric = metric;
-          for x in zero_restored.iter() {
-            complex.zeroed_cache.remove(x);
-          }
-          for (k, v) in zeroed_cache.into_iter() {
-            complex.zeroed_cache.insert(k, v);
+      let mut output = None;
+      let mut started = false;
+      with_(&mut |complex| {
+        if let Some(pressed) = pressed_.get() {
+          let client_pos = if complex.lateral { e.client_x() as f64 } else { e.client_y() as f64 };
+          if (client_pos-pressed).abs()<DRAG_THRESHOLD {
+            return;
           }
-        });
+          pressed_.set(None);
+          started = true;
+        }
+        output = complex.resize_work(e.clone(), &data_, pos, shift, cache, wrap_ref, resizer_ref, ref_get::<_, Element>).ok();
+      });
+      // set signals out of `with`, which may be borrowing one of them
+      if started {
+        resizing.set(true);
+      }
+      if let Some(output) = output {
+        apply_(output);
       }
     };
 
+    let pressed_ = pressed.clone();
     let pointer_up = move |_| {
       unsafe {
         (*cache).clear();
         let _ = (*shift).take();
         let _ = (*pos).take();
       }
-      resizing.set(false);
+      pressed_.set(None);
+      if resizing.get_untracked() {
+        resizing.set(false);
+      }
     };
 
+    let (with_, animation_) = (with.clone(), animation.clone());
     let pointer_down = move |e: PointerEvent| {
-      complex.with(|complex| {
+      animation_.cancel();
+      with_(&mut |complex| {
         unsafe {
           if let Some(resizer) = ref_get::<_, Element>(resizer_ref) {
             let front = get_elem_front(resizer, complex.lateral);
             let client_pos = if complex.lateral { e.client_x() as f64 } else { e.client_y() as f64 };
             let _ = (*shift).replace(client_pos - front);
             let _ = (*pos).replace(client_pos);
+            pressed.set(Some(client_pos));
           }
         }
       });
-      resizing.set(true);
     };
 
+    // discrete works run on a clone, as their outputs may be applied at once or later by the transition.
+    let cloned = |with: &ComplexWith<T>| {
+      let mut cloned = None;
+      with(&mut |complex| cloned = Some(complex.clone()));
+      cloned
+    };
+
+    let (with_, data_, apply_, animation_) = (with.clone(), data.clone(), apply.clone(), animation.clone());
+    let cb_keydown = Closure::<dyn FnMut(_)>::new(move |e: web_sys::KeyboardEvent| {
+      // start from the former animation's final state
+      animation_.finish();
+      let (Some(complex), Some(wrap)) = (cloned(&with_), ref_get::<_, Element>(wrap_ref)) else { return };
+      let result = if e.key()=="Enter" {
+        complex.with_transition(&wrap, |wrap| complex.toggled_zero(wrap, &data_), apply_.clone())
+          .map(|x| animation_.replace(x))
+      } else {
+        complex.key_resize_work(&e, &data_, wrap_ref, ref_get::<_, Element>).map(&apply_)
+      };
+      if result.is_ok() {
+        e.prevent_default();
+      }
+    });
+
+    let (with_, data_, apply_) = (with.clone(), data.clone(), apply.clone());
+    let cb_dblclick = Closure::<dyn FnMut(_)>::new(move |e: web_sys::MouseEvent| {
+      animation.finish();
+      let (Some(complex), Some(wrap)) = (cloned(&with_), ref_get::<_, Element>(wrap_ref)) else { return };
+      if let Ok(x) = complex.with_transition(&wrap, |wrap| complex.dblclick_on(wrap, &data_), apply_.clone()) {
+        animation.replace(x);
+        e.prevent_default();
+      }
+    });
+
     let (cb_pointerdown, raws) = pointer_down_move_up(pointer_down, pointer_move, pointer_up);
 
 
     on_mount(move || {
       ref_get::<_, EventTarget>(resizer_ref).map(|resizer| {
         resizer.add_event_listener_with_callback("pointerdown", cb_pointerdown.as_ref().unchecked_ref()).unwrap_throw();
+        resizer.add_event_listener_with_callback("keydown", cb_keydown.as_ref().unchecked_ref()).unwrap_throw();
+        resizer.add_event_listener_with_callback("dblclick", cb_dblclick.as_ref().unchecked_ref()).unwrap_throw();
         
         on_cleanup(move || {
           resizer.remove_event_listener_with_callback("pointerdown", cb_pointerdown.as_ref().unchecked_ref()).unwrap_throw();
+          resizer.remove_event_listener_with_callback("keydown", cb_keydown.as_ref().unchecked_ref()).unwrap_throw();
+          resizer.remove_event_listener_with_callback("dblclick", cb_dblclick.as_ref().unchecked_ref()).unwrap_throw();
+        });
+      });
+
+      // keep ARIA attributes in sync with the metric
+      create_effect(move || {
+        with(&mut |complex| {
+          if let Some(resizer) = ref_get::<_, Element>(resizer_ref) {
+            complex.set_resizer_aria(resizer, &data);
+          }
         });
       });
 
@@ -0,0 +1,266 @@
+//! Nested complexes of cartons, as docking layouts
+//!
+//! A [`CartonsComplex`] handles one flat row or column of cartons.
+//! Layouts like an IDE's workspace (sidebar | editor over terminal | inspector) nest complexes into each other.
+//!
+//! [`CartonsTree`] is a complex with nested trees:
+//! * A carton with a nested tree is the wrap element of the nested complex. Its children are the nested cartons.
+//! * Other cartons are just cartons.
+//! * Each complex has its own `lateral` direction and sizing rules.
+//!
+//! When an outer complex is updated, by the wrap's update or its resizers, nested complexes are updated too.
+//! The whole tree's metric is one serializable state, [`CartonsTreeMetric`].
+//!
+//! Nested cartons are found by each complex's dataset name among direct children of its wrap,
+//! so nested complexes may share the dataset name of their outer complex.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! fn complex(lateral: bool, metric: Vec<(usize, Option<Sizon>)>) -> CartonsComplex<usize> {
+//!   CartonsComplex::new(
+//!     lateral, false, None,
+//!     metric.into(), (vec![], Sizon::abs(100.)).into(), (vec![], Sizon::rel(1.)).into(),
+//!     vec![].into(), vec![].into(), vec![].into()
+//!   )
+//! }
+//!
+//! // sidebar(0) | editor(10) over terminal(11) | inspector(2)
+//! let center = CartonsTree::new(complex(false, vec![(10, Some(Sizon::rel(0.7))), (11, Some(Sizon::rel(0.3)))]));
+//! let mut tree = CartonsTree::new(complex(true, vec![(0, Some(Sizon::rel(0.2))), (1, None), (2, Some(Sizon::rel(0.2)))]))
+//!   .nest(1, center);
+//!
+//! assert!(!tree.get(&[1]).unwrap().complex.lateral);
+//! assert!(tree.get(&[0]).is_none());
+//!
+//! // restore the whole tree's state at once, zeroed caches included
+//! let mut metric = tree.metric();
+//! let center = metric.nested.get_mut(&1).unwrap();
+//! center.metric.insert(11, Some(Sizon::abs(0.)));
+//! center.zeroed_cache.insert(11, 0.3);
+//! tree.set_metric(metric);
+//! let center = &tree.get(&[1]).unwrap().complex;
+//! assert_eq!(*center.metric.get(&11), Some(Sizon::abs(0.)));
+//! assert_eq!(*center.zeroed_cache.get(&11), 0.3);
+//! ```
+
+use super::*;
+
+/// Metric of a [`CartonsTree`]: metric and zeroed cache of the complex, and metrics of nested trees by their cartons' dataset values.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct CartonsTreeMetric<T: Eq + Hash + FromStr + Clone> {
+  pub metric: CartonsMetric<T>,
+  /// former sizes of zeroed cartons, so that they're restored after all. Check [`CartonsComplex`]'s `zeroed_cache`
+  pub zeroed_cache: CartonsMap<T, f64>,
+  pub nested: HashMap<T, CartonsTreeMetric<T>>,
+}
+
+/// A complex of cartons with nested trees. Check the [module](crate::cartons::tree) doc.
+#[derive(Debug, Clone)]
+pub struct CartonsTree<T: Eq + Hash + FromStr + Clone> {
+  /// complex of this level
+  pub complex: CartonsComplex<T>,
+  /// nested trees by their cartons' dataset values
+  pub nested: HashMap<T, CartonsTree<T>>,
+}
+
+impl<T: Eq + Hash + FromStr + Clone + std::fmt::Debug> CartonsTree<T> {
+
+  /// New tree without nested ones
+  pub fn new(complex: CartonsComplex<T>) -> Self {
+    Self { complex, nested: HashMap::new() }
+  }
+
+  /// Nest a tree into the carton of `data`. The carton element will be the wrap of the nested complex.
+  pub fn nest(mut self, data: T, tree: Self) -> Self {
+    self.nested.insert(data, tree);
+    self
+  }
+
+  /// Get a nested tree by the path of cartons' dataset values, from the outermost. Empty path refers to self.
+  pub fn get(&self, path: &[T]) -> Option<&Self> {
+    match path.split_first() {
+      Some((data, path)) => self.nested.get(data).and_then(|tree| tree.get(path)),
+      None => Some(self),
+    }
+  }
+
+  /// Get a mutable nested tree by the path of cartons' dataset values, from the outermost.
+  pub fn get_mut(&mut self, path: &[T]) -> Option<&mut Self> {
+    match path.split_first() {
+      Some((data, path)) => self.nested.get_mut(data).and_then(|tree| tree.get_mut(path)),
+      None => Some(self),
+    }
+  }
+
+  /// Whole tree's metric
+  pub fn metric(&self) -> CartonsTreeMetric<T> {
+    CartonsTreeMetric {
+      metric: self.complex.metric.clone(),
+      zeroed_cache: self.complex.zeroed_cache.clone(),
+      nested: self.nested.iter().map(|(data, tree)| (data.clone(), tree.metric())).collect(),
+    }
+  }
+
+  /// Set whole tree's metric. Metrics of trees which are not nested in self are ignored.
+  pub fn set_metric(&mut self, metric: CartonsTreeMetric<T>) {
+    self.complex.metric = metric.metric;
+    self.complex.zeroed_cache = metric.zeroed_cache;
+    self.set_nested_metric(metric.nested);
+  }
+
+  fn set_nested_metric(&mut self, nested: HashMap<T, CartonsTreeMetric<T>>) {
+    for (data, metric) in nested.into_iter() {
+      if let Some(tree) = self.nested.get_mut(&data) {
+        tree.set_metric(metric);
+      }
+    }
+  }
+
+  /// Run nested complexes' wrap effect, in cartons of the wrap element
+  fn nested_effect<E: AsRef<Element>>(&self, wrap: E) -> HashMap<T, CartonsTreeMetric<T>> {
+
+    if self.nested.is_empty() {
+      return HashMap::new();
+    }
+
+    let (elems, datas) = CartonsComplex::<T>::wrap_to_carton_elems(wrap, self.complex.name);
+
+    elems.into_iter().zip(datas).filter_map(|(elem, data)| {
+      self.nested.get(&data).map(|tree| {
+        let metric = CartonsTreeMetric {
+          metric: tree.complex.wrap_effect(&elem),
+          zeroed_cache: tree.complex.zeroed_cache.clone(),
+          nested: tree.nested_effect(&elem),
+        };
+        (data, metric)
+      })
+    }).collect()
+  }
+
+  /// Whole tree's effect on any possible update, like [`CartonsComplex::wrap_effect_on_update()`].
+  /// Outer complexes are updated first, then nested ones are updated within the new sizes.
+  ///
+  /// Return new metric of the whole tree.
+  pub fn wrap_effect_on_update<X: Copy + 'static, E: AsRef<Element>>(
+    &self,
+    wrap: X,
+    get_elem: impl Fn(X) -> Option<E> + Copy + 'static,
+  ) -> Result<CartonsTreeMetric<T>> {
+
+    let Some(wrap) = get_elem(wrap) else { return Err(Error::Ignore) };
+    let metric = self.complex.wrap_effect(&wrap);
+    let nested = self.nested_effect(&wrap);
+    Ok(CartonsTreeMetric { metric, zeroed_cache: self.complex.zeroed_cache.clone(), nested })
+  }
+
+  /// Nested complexes' effect after the complex of self is updated, like by its resizers.
+  ///
+  /// Return new metrics of nested trees.
+  pub fn nested_effect_on_update<X: Copy + 'static, E: AsRef<Element>>(
+    &self,
+    wrap: X,
+    get_elem: impl Fn(X) -> Option<E> + Copy + 'static,
+  ) -> Result<HashMap<T, CartonsTreeMetric<T>>> {
+
+    let Some(wrap) = get_elem(wrap) else { return Err(Error::Ignore) };
+    Ok(self.nested_effect(wrap))
+  }
+
+  /// Expand [`wrap_effect_on_update()`] for Sycamore, like [`CartonsComplex::init_wrap()`].
+  ///
+  /// # Args
+  /// * tree: the signal of CartonsTree
+  /// * wrap_ref: outermost wrapping element's NodeRef
+  /// * update_by: tuple of signals which can effect sizing states. Ex. window_resizing signal
+  ///
+  /// # Outputs
+  /// * wrap_ref
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn init_wrap<G: GenericNode, U: Trackable + 'static>(
+    tree: Signal<Self>,
+    wrap_ref: Option<NodeRef<G>>,
+    update_by: U
+  ) -> NodeRef<G> {
+    let wrap_ref = wrap_ref.unwrap_or(create_node_ref());
+
+    on_mount(move || {
+      create_effect(on(update_by, move || {
+        if let Ok(metric) = tree.with_untracked(|tree| tree.wrap_effect_on_update(wrap_ref, ref_get::<_, Element>)) {
+          tree.update(|tree| tree.set_metric(metric));
+        }
+      }));
+    });
+
+    wrap_ref
+  }
+
+  /// Initiate a resizer of a carton, in the complex at `path`. (Check [`get()`](Self::get) about the path)
+  /// Like [`CartonsComplex::init_resizer()`], but nested complexes of the resized complex are updated together.
+  ///
+  /// # Args
+  /// * tree: the signal of CartonsTree
+  /// * path: path to the complex of the carton
+  /// * wrap_ref: NodeRef of the complex's wrapping element. (For nested ones, it's the carton element they're nested into)
+  /// * resizer_ref, data, resizing: same with `CartonsComplex::init_resizer()`
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// # use sycamore::prelude::*;
+  /// #[component(inline_props)]
+  /// fn Workspace<G: Html>(tree: Signal<CartonsTree<usize>>) -> View<G> {
+  ///   let WindowResizing(window_resizing) = use_context();
+  ///   let wrap_ref = CartonsTree::init_wrap(tree, None, window_resizing);
+  ///   let center_ref = create_node_ref();
+  ///   let (sidebar_resizer, _) = CartonsTree::init_resizer(tree, vec![], wrap_ref, None, 0, None);
+  ///   let (editor_resizer, _) = CartonsTree::init_resizer(tree, vec![1], center_ref, None, 10, None);
+  ///
+  ///   view! {
+  ///     div(ref=wrap_ref, style="position: relative; display: flex;") {
+  ///       div(data-carton="0") { "sidebar" div(ref=sidebar_resizer) }
+  ///       div(ref=center_ref, data-carton="1", style="position: relative;") {
+  ///         div(data-carton="10") { "editor" div(ref=editor_resizer) }
+  ///         div(data-carton="11") { "terminal" }
+  ///       }
+  ///       div(data-carton="2") { "inspector" }
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn init_resizer<G: GenericNode>(
+    tree: Signal<Self>,
+    path: Vec<T>,
+    wrap_ref: NodeRef<G>,
+    resizer_ref: Option<NodeRef<G>>,
+    data: T,
+    resizing: Option<Signal<bool>>
+  ) -> (
+    NodeRef<G>,
+    Signal<bool>
+  ) {
+    let path_ = path.clone();
+
+    CartonsComplex::init_resizer_with(
+      move |work| tree.with(|tree| {
+        if let Some(tree) = tree.get(&path_) {
+          work(&tree.complex);
+        }
+      }),
+      move |(metric, zeroed_cache, zero_restored)| tree.update(|tree| {
+        if let Some(tree) = tree.get_mut(&path) {
+          tree.complex.apply_resized(metric, zeroed_cache, zero_restored);
+          if let Ok(nested) = tree.nested_effect_on_update(wrap_ref, ref_get::<_, Element>) {
+            tree.set_nested_metric(nested);
+          }
+        }
+      }),
+      wrap_ref, resizer_ref, data, resizing
+    )
+  }
+}
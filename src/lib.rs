@@ -5,6 +5,7 @@
 //! * **Resizing** parallel panels => mod [`cartons`]
 //! * **Reactive positioning** of tooltips or menubars => mod [`possize`]
 //! * and [`sizon`]
+//! * DOM-free distribution of space with Sizon limits => mod [`solver`]
 //! 
 //! 
 //! ## Features
@@ -18,6 +19,7 @@ pub mod utils;
 pub mod error;
 use utils::*;
 use error::{Error, Result};
+pub use utils::Rect;
 
 #[cfg(feature="sycamore")]
 pub use utils::WindowResizing;
@@ -28,6 +30,9 @@ pub use utils::LeptosWindowResizing;
 pub mod sizon;
 pub use sizon::*;
 
+pub mod solver;
+pub use solver::*;
+
 pub mod possize;
 pub use possize::*;
 
@@ -16,12 +16,60 @@
 //!   * ancestor's size and position, 
 //!   * and pre-assinged configures like drawing tooltip/menubar's size, margin, and their relative position to its ancestor.
 //! 
-//! For tooltips with {position: absolute}, use `AbsPosSize`.
-//! 
-//! For context menubars with {position: fixed}, use `FixedPosSize`.
+//! For tooltips with {position: absolute}, use `AbsPosSize`. For context menubars with {position: fixed}, use `FixedPosSize`.
+//! Besides them:
+//! * Placing: named placements with flip and shift([`PlacePosSize`]), builders, [`Boundary`], [`Anchor`]s other than elements, [`fit`] and [`measure`].
+//! * Styling: [`Arrow`], [`PosStyle`] for `transform` or right-to-left, and [`HideFlags`].
+//! * Following: [`AutoUpdate`] on scroll or resize, and [`FollowCursor`].
+//! * Ready made ones: [`ContextMenu`], [`Tooltip`], and [`SubmenuPosSize`] with [`HoverIntent`] for nested menus.
 
 use crate::*;
 
+pub mod placement;
+pub use placement::*;
+
+pub mod arrow;
+pub use arrow::*;
+
+pub mod boundary;
+pub use boundary::*;
+
+pub mod measure;
+pub use measure::*;
+
+pub mod auto_update;
+pub use auto_update::*;
+
+pub mod anchor;
+pub use anchor::*;
+
+pub mod fit;
+pub use fit::*;
+
+pub mod output;
+pub use output::*;
+
+pub mod submenu;
+pub use submenu::*;
+
+pub mod intent;
+pub use intent::*;
+
+pub mod context_menu;
+pub use context_menu::*;
+
+pub mod tooltip;
+pub use tooltip::*;
+
+pub mod follow;
+pub use follow::*;
+
+pub mod hide;
+pub use hide::*;
+
+pub mod builder;
+pub use builder::*;
+
 /// trait for `AbsUniPosSize` and `FixedUniPosSize`
 pub trait UniPosSize {
 
@@ -52,6 +100,19 @@ pub trait UniPosSize {
     front_fixed_pos + self.size() > doc_size - self.rear_margin()
   }
 
+  /// How much does a relevant element's size go over browser's client range? Zero when it's not over.
+  /// 
+  /// Sum of overs at front side and rear side. Check [`is_over()`](Self::is_over)
+  fn overflow(&self, front_fixed_pos: f64, doc_size: f64) -> f64 {
+    (self.front_margin() - front_fixed_pos).max(0.) +
+    (front_fixed_pos + self.size() - (doc_size - self.rear_margin())).max(0.)
+  }
+
+  /// Maximum size to stay inside of browser's client range: document size excluding margins.
+  fn max_size(&self, doc_size: f64) -> f64 {
+    (doc_size - self.front_margin() - self.rear_margin()).max(0.)
+  }
+
   /// Adjust given `front_fixed_pos` not to go over browser's client range.
   /// 
   /// Not exceeding the front side has a higher priority than not excedding the rear side.
@@ -74,6 +135,7 @@ pub trait UniPosSize {
 /// 
 /// A relevant element is supposed to have style { position: fixed; }
 #[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct FixedUniPosSize  {
   /// one's pre-assigned size
   pub size: f64,
@@ -117,7 +179,10 @@ impl FixedUniPosSize {
 ///   * outward: is the element spreading outwards or inwards in respect to its ancestor?
 ///   * gap: gap between oneself and ancestor's front/rear. Use [`Sizon`].
 ///     - When trying to get a value from sizon, the priority is: 1) field `abs` 2) `rel` 3) fallback return 0.
+///
+/// With serde, `front` and `outward` are (de)serialized as a named side `at`. (Check [`AbsUniAt`])
 #[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "AbsUniPosSizeConfig<AbsUniAt>", into = "AbsUniPosSizeConfig<AbsUniAt>")]
 pub struct AbsUniPosSize  {
   /// from ancestor's front/rear
   pub front: bool,
@@ -156,34 +221,71 @@ impl AbsUniPosSize {
     doc_size: f64
   ) -> f64 {
 
-    // get absolute(not relative; in the context of Sizon, not css position) metric gap from sizon.
-    // trying order: 1) abs 2) rel 3) fallback return 0.
-    let get_actual_gap = move || -> f64 {
-      if let Some(abs) = self.gap.abs {
-        abs
-      } else if let Some(rel) = self.gap.rel.filter(|rel| !rel.is_nan()) {
-        ancestor_size * rel
-      } else {
-        0.
-      }
-    };
+    let opposite = self.is_opposite_chosen(ancestor_front_pos, ancestor_size, doc_size);
+    let mut front_fixed_pos = self.raw_front_fixed_pos(opposite, ancestor_front_pos, ancestor_size);
 
-    let get_front_fixed_pos = move |opposite: bool| -> f64 {
+    if self.is_over(front_fixed_pos, doc_size) {
+      self.adjust_front_pos(&mut front_fixed_pos, doc_size);
+    }
 
-      let front = if opposite { !self.front } else { self.front };
+    front_fixed_pos
+  }
 
-      let gap = get_actual_gap();
-      let mut key_pos = ancestor_front_pos;
-      if !front {
-        key_pos += ancestor_size;
-      }
-      if front==self.outward {
-        key_pos -= gap + self.size;
-      } else {
-        key_pos += gap;
-      }
-      key_pos
+  /// Available size on the side chosen by [`front_absolute_to_fixed_pos()`](Self::front_absolute_to_fixed_pos):
+  /// space between the ancestor's side(with gap) and the document's edge(with margin).
+  /// 
+  /// When it's smaller than `self.size`, the element can't help going over the document's client range.
+  pub fn available_size(
+    &self,
+    ancestor_front_pos: f64,
+    ancestor_size: f64,
+    doc_size: f64
+  ) -> f64 {
+    let opposite = self.is_opposite_chosen(ancestor_front_pos, ancestor_size, doc_size);
+    let front = if opposite { !self.front } else { self.front };
+    let front_fixed_pos = self.raw_front_fixed_pos(opposite, ancestor_front_pos, ancestor_size);
+
+    let available = if front==self.outward {
+      // spreading toward the front
+      front_fixed_pos + self.size - self.front_margin
+    } else {
+      doc_size - self.rear_margin - front_fixed_pos
     };
+    available.max(0.)
+  }
+
+  /// get absolute(not relative; in the context of Sizon, not css position) metric gap from sizon.
+  /// trying order: 1) abs 2) rel 3) fallback return 0.
+  fn actual_gap(&self, ancestor_size: f64) -> f64 {
+    if let Some(abs) = self.gap.abs {
+      abs
+    } else if let Some(rel) = self.gap.rel.filter(|rel| !rel.is_nan()) {
+      ancestor_size * rel
+    } else {
+      0.
+    }
+  }
+
+  /// front_fixed_pos without any adjustment, at the configured side or its opposite side.
+  fn raw_front_fixed_pos(&self, opposite: bool, ancestor_front_pos: f64, ancestor_size: f64) -> f64 {
+
+    let front = if opposite { !self.front } else { self.front };
+
+    let gap = self.actual_gap(ancestor_size);
+    let mut key_pos = ancestor_front_pos;
+    if !front {
+      key_pos += ancestor_size;
+    }
+    if front==self.outward {
+      key_pos -= gap + self.size;
+    } else {
+      key_pos += gap;
+    }
+    key_pos
+  }
+
+  /// Should the element go to the opposite side? Only outward elements going over the document's client range would try it.
+  fn is_opposite_chosen(&self, ancestor_front_pos: f64, ancestor_size: f64, doc_size: f64) -> bool {
 
     let is_opposite_better = move || -> bool {
       
@@ -197,18 +299,8 @@ impl AbsUniPosSize {
       }
     };
 
-    let mut front_fixed_pos = get_front_fixed_pos(false);
-
-    if self.is_over(front_fixed_pos, doc_size) {
-      if self.outward {
-        if is_opposite_better() {
-          front_fixed_pos = get_front_fixed_pos(true);
-        }
-      }
-      self.adjust_front_pos(&mut front_fixed_pos, doc_size);
-    }
-
-    front_fixed_pos
+    let front_fixed_pos = self.raw_front_fixed_pos(false, ancestor_front_pos, ancestor_size);
+    self.is_over(front_fixed_pos, doc_size) && self.outward && is_opposite_better()
   }
 }
 
@@ -218,7 +310,8 @@ impl AbsUniPosSize {
 /// A relevant element is supposed to have style { position: fixed; }
 /// 
 /// Check out `FixedUniPosSize`
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct FixedPosSize {
   lateral: FixedUniPosSize,
   vertical: FixedUniPosSize
@@ -241,28 +334,27 @@ impl FixedPosSize {
     }
   }
 
-  /// Return adjusted front_fixed_pos(`left` and `top`) of an element, considering given position and document size
-  pub fn front_fixed_pos(&self, (client_x, client_y): (f64, f64)) -> (f64, f64) {
-    
-    let doc = gloo_utils::document_element();
-    let (doc_height, doc_width) = (doc.client_height() as f64, doc.client_width() as f64);
+  /// lateral size and margins
+  pub fn lateral(&self) -> &FixedUniPosSize { &self.lateral }
+
+  /// vertical size and margins
+  pub fn vertical(&self) -> &FixedUniPosSize { &self.vertical }
+
+  pub fn lateral_mut(&mut self) -> &mut FixedUniPosSize { &mut self.lateral }
+
+  pub fn vertical_mut(&mut self) -> &mut FixedUniPosSize { &mut self.vertical }
 
-    let fixed_left = self.lateral.front_fixed_pos(client_x, doc_width);
-    let fixed_top = self.vertical.front_fixed_pos(client_y, doc_height);
-    (fixed_left, fixed_top)
+  /// (width, height)
+  pub fn size(&self) -> (f64, f64) { (self.lateral.size, self.vertical.size) }
+
+  /// Return adjusted front_fixed_pos(`left` and `top`) of an element, considering given position and document size
+  pub fn front_fixed_pos(&self, client_xy: (f64, f64)) -> (f64, f64) {
+    self.front_fixed_pos_in(client_xy, &Boundary::Document)
   }
 
   /// Set style of fixed positions and sizes
   pub fn set_style<H: AsRef<HtmlElement>>(&self, elem: H, client_xy: (f64, f64)) {
-
-    let (fixed_left, fixed_top) = self.front_fixed_pos(client_xy);
-    let (width, height) = (self.lateral.size, self.vertical.size);
-
-    let style = elem.as_ref().style();
-    let _ = style.set_property("top", &format!("{:.2}px", fixed_top));
-    let _ = style.set_property("height", &format!("{:.2}px", height));
-    let _ = style.set_property("left", &format!("{:.2}px", fixed_left));
-    let _ = style.set_property("width", &format!("{:.2}px", width));
+    self.set_style_in(elem, client_xy, &Boundary::Document)
   }
 }
 
@@ -278,7 +370,10 @@ impl FixedPosSize {
 ///   * gap: gap between oneself and ancestor's front/rear. Use `Sizon`.
 /// 
 /// Check out `AbsUniPosSize`
+///
+/// Default and serde's config are same with [`AbsPosSize::builder()`]. (Check [`AbsPosSizeConfig`])
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "AbsPosSizeConfig", into = "AbsPosSizeConfig")]
 pub struct AbsPosSize {
   lateral: AbsUniPosSize,
   vertical: AbsUniPosSize
@@ -313,33 +408,31 @@ impl AbsPosSize {
     }
   }
 
+  /// lateral position, size and margins
+  pub fn lateral(&self) -> &AbsUniPosSize { &self.lateral }
+
+  /// vertical position, size and margins
+  pub fn vertical(&self) -> &AbsUniPosSize { &self.vertical }
+
+  pub fn lateral_mut(&mut self) -> &mut AbsUniPosSize { &mut self.lateral }
+
+  pub fn vertical_mut(&mut self) -> &mut AbsUniPosSize { &mut self.vertical }
+
+  /// (width, height)
+  pub fn size(&self) -> (f64, f64) { (self.lateral.size, self.vertical.size) }
+
   /// Return adjusted absolute front pos(`left` and `top`) of an element,  
   /// considering given ancestor's position and size, and document's size
   pub fn front_absolute_pos<E: AsRef<Element>>(
     &self,
     ancestor: E
   ) -> (f64, f64) {
-    let doc = gloo_utils::document_element();
-    let (doc_width, doc_height) = (doc.client_width() as f64, doc.client_height() as f64);
-
-    let (ancestor_top, ancestor_height, ancestor_left, ancestor_width) = get_rect_thlw(ancestor);
-    let fixed_left = self.lateral.front_absolute_to_fixed_pos(ancestor_left, ancestor_width, doc_width);
-    let fixed_top = self.vertical.front_absolute_to_fixed_pos(ancestor_top, ancestor_height, doc_height); 
-    
-    (fixed_left-ancestor_left, fixed_top-ancestor_top)
+    self.front_absolute_pos_in(ancestor, &Boundary::Document)
   }
 
 
   /// Set style of absolute positions and sizes
   pub fn set_style<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H) {
-
-    let (abs_left, abs_top) = self.front_absolute_pos(ancestor);
-    let (width, height) = (self.lateral.size, self.vertical.size);
-
-    let style = elem.as_ref().style();
-    let _ = style.set_property("top", &format!("{:.2}px", abs_top));
-    let _ = style.set_property("height", &format!("{:.2}px", height));
-    let _ = style.set_property("left", &format!("{:.2}px", abs_left));
-    let _ = style.set_property("width", &format!("{:.2}px", width));
+    self.set_style_in(ancestor, elem, &Boundary::Document)
   }
-}
\ No newline at end of file
+}
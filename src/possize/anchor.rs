@@ -0,0 +1,230 @@
+//! Virtual anchors of floating elements
+//!
+//! Floating elements don't always hang off an element.
+//! A "format selection" toolbar follows selected text, and an autocomplete popup follows the text caret.
+//!
+//! [`Anchor`] resolves into a rect in client coordinates from:
+//! * an element
+//! * a point (Ex. mouse click position)
+//! * a rect, or a `DOMRect`
+//! * a DOM `Range` (Ex. text selection: [`Anchor::selection()`])
+//! * the caret position inside of a `textarea` or `input` (`From` them)
+//!
+//! Anchors work with every possize struct, placing floating elements for { position: fixed }:
+//! * [`AbsPosSize::front_fixed_pos_at()`]
+//! * [`FixedPosSize::front_fixed_pos_at()`]: at the anchor's [`point()`](Anchor::point)
+//! * [`PlacePosSize::placed_at()`]
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let possize = PlacePosSize::new(Placement::BottomStart, Sizon::abs(4.), (200., 10., 10.), (100., 10., 10.));
+//! let boundary = Boundary::Rect(Rect::new(0., 0., 800., 600.));
+//!
+//! let anchor = Anchor::from((300., 200.));
+//! let placed = possize.placed_at(&anchor, &boundary);
+//! assert_eq!(placed.rect, Rect::new(300., 204., 200., 100.));
+//!
+//! // caret-like zero width rect at the bottom of the boundary => flipped to the top
+//! let anchor = Anchor::from(Rect::new(300., 560., 0., 20.));
+//! let placed = possize.placed_at(&anchor, &boundary);
+//! assert_eq!(placed.placement, Placement::TopStart);
+//! assert_eq!(placed.rect, Rect::new(300., 456., 200., 100.));
+//! ```
+
+use super::*;
+use web_sys::{DomRect, Range, HtmlTextAreaElement, HtmlInputElement};
+
+/// Anchor of a floating element
+#[derive(Debug, Clone, PartialEq)]
+pub enum Anchor {
+  /// an element's rect
+  Element(Element),
+  /// a point in client coordinates: zero sized rect
+  Point(f64, f64),
+  /// a rect in client coordinates
+  Rect(Rect),
+  /// a DOM range's rect
+  Range(Range),
+  /// rect of the caret(selection end) inside of a `textarea` or `input` element: zero width with a line's height.
+  Caret(Element),
+}
+
+impl From<Element> for Anchor {
+  fn from(value: Element) -> Self { Self::Element(value) }
+}
+
+impl From<HtmlElement> for Anchor {
+  fn from(value: HtmlElement) -> Self { Self::Element(value.into()) }
+}
+
+impl From<(f64, f64)> for Anchor {
+  fn from((x, y): (f64, f64)) -> Self { Self::Point(x, y) }
+}
+
+impl From<Rect> for Anchor {
+  fn from(value: Rect) -> Self { Self::Rect(value) }
+}
+
+impl From<DomRect> for Anchor {
+  fn from(value: DomRect) -> Self { Self::Rect(value.into()) }
+}
+
+impl From<Range> for Anchor {
+  fn from(value: Range) -> Self { Self::Range(value) }
+}
+
+impl From<HtmlTextAreaElement> for Anchor {
+  fn from(value: HtmlTextAreaElement) -> Self { Self::Caret(value.into()) }
+}
+
+impl From<HtmlInputElement> for Anchor {
+  fn from(value: HtmlInputElement) -> Self { Self::Caret(value.into()) }
+}
+
+impl Anchor {
+
+  /// Anchor of the document's current text selection, if any.
+  pub fn selection() -> Option<Self> {
+    let selection = gloo_utils::window().get_selection().ok()??;
+    if selection.range_count()==0 {
+      return None;
+    }
+    selection.get_range_at(0).ok().map(Self::Range)
+  }
+
+  /// Resolve the anchor into a rect in client coordinates
+  pub fn rect(&self) -> Rect {
+    match self {
+      Self::Element(elem) => Rect::elem(elem),
+      Self::Point(x, y) => Rect::new(*x, *y, 0., 0.),
+      Self::Rect(rect) => *rect,
+      Self::Range(range) => range.get_bounding_client_rect().into(),
+      Self::Caret(elem) => get_caret_rect(elem).unwrap_or_else(|| Rect::elem(elem)),
+    }
+  }
+
+  /// Resolve the anchor into a point in client coordinates: left-bottom of its rect.
+  /// (Ex. a context menu opens below the caret)
+  pub fn point(&self) -> (f64, f64) {
+    match self {
+      Self::Point(x, y) => (*x, *y),
+      _ => {
+        let rect = self.rect();
+        (rect.left, rect.bottom())
+      }
+    }
+  }
+}
+
+/// css properties affecting text layout, to be copied into the mirror element of [`get_caret_rect()`]
+const MIRROR_PROPERTIES: [&str; 28] = [
+  "direction", "box-sizing", "width", "height", "overflow-x", "overflow-y",
+  "border-top-width", "border-right-width", "border-bottom-width", "border-left-width", "border-style",
+  "padding-top", "padding-right", "padding-bottom", "padding-left",
+  "font-style", "font-variant", "font-weight", "font-stretch", "font-size", "line-height", "font-family",
+  "text-align", "text-transform", "text-indent", "letter-spacing", "word-spacing", "tab-size",
+];
+
+/// Get rect of the caret(selection end) inside of a `textarea` or `input` element, in client coordinates.
+///
+/// The element's text until the caret is rendered into a hidden mirror element copying its text layout,
+/// and the position of a marker after the text is measured.
+///
+/// Return None when the element is neither `textarea` nor `input`, or it has no selection.
+pub fn get_caret_rect<E: AsRef<Element>>(elem: E) -> Option<Rect> {
+  let elem = elem.as_ref();
+
+  let (value, caret, multiline) = if let Some(x) = elem.dyn_ref::<HtmlTextAreaElement>() {
+    (x.value(), x.selection_end().ok()??, true)
+  } else if let Some(x) = elem.dyn_ref::<HtmlInputElement>() {
+    (x.value(), x.selection_end().ok()??, false)
+  } else {
+    return None;
+  };
+
+  // selection is indexed by UTF-16 code units
+  let before: Vec<u16> = value.encode_utf16().take(caret as usize).collect();
+  let before = String::from_utf16_lossy(&before);
+
+  let document = gloo_utils::document();
+  let computed = gloo_utils::window().get_computed_style(elem).ok()??;
+  let mirror: HtmlElement = document.create_element("div").ok()?.unchecked_into();
+  let marker: HtmlElement = document.create_element("span").ok()?.unchecked_into();
+
+  let style = mirror.style();
+  for prop in MIRROR_PROPERTIES {
+    if let Ok(value) = computed.get_property_value(prop) {
+      let _ = style.set_property(prop, &value);
+    }
+  }
+  let _ = style.set_property("position", "absolute");
+  let _ = style.set_property("visibility", "hidden");
+  let _ = style.set_property("top", "0px");
+  let _ = style.set_property("left", "-9999px");
+  let _ = style.set_property("white-space", if multiline { "pre-wrap" } else { "pre" });
+  let _ = style.set_property("overflow-wrap", "break-word");
+
+  mirror.set_text_content(Some(&before));
+  // marker needs any content to have a line's height
+  marker.set_text_content(Some("\u{200b}"));
+  let _ = mirror.append_child(&marker);
+  let _ = gloo_utils::body().append_child(&mirror);
+
+  let (marker_left, marker_top, marker_height) =
+    (marker.offset_left() as f64, marker.offset_top() as f64, marker.offset_height() as f64);
+  mirror.remove();
+
+  // marker's offset is from the mirror's padding edge: add the element's border, and the mirror doesn't scroll.
+  let rect = Rect::elem(elem);
+  Some(Rect::new(
+    rect.left + elem.client_left() as f64 + marker_left - elem.scroll_left() as f64,
+    rect.top + elem.client_top() as f64 + marker_top - elem.scroll_top() as f64,
+    0.,
+    marker_height
+  ))
+}
+
+
+impl AbsPosSize {
+
+  /// Return adjusted front pos(`left` and `top`) of an element in client coordinates, around the anchor inside of the boundary.
+  /// The element is supposed to be { position: fixed }.
+  pub fn front_fixed_pos_at(&self, anchor: &Anchor, boundary: &Boundary) -> (f64, f64) {
+    self.front_fixed_pos_around(anchor.rect(), boundary.rect())
+  }
+
+  /// Set style of fixed positions and sizes around the anchor inside of the boundary
+  pub fn set_fixed_style_at<H: AsRef<HtmlElement>>(&self, anchor: &Anchor, elem: H, boundary: &Boundary) {
+    let (left, top) = self.front_fixed_pos_at(anchor, boundary);
+    PosStyle::new().set_style(elem, Rect::new(left, top, self.lateral.size, self.vertical.size), 0.);
+  }
+}
+
+impl FixedPosSize {
+
+  /// Return adjusted front_fixed_pos(`left` and `top`) of an element at the anchor's [`point()`](Anchor::point), inside of the boundary
+  pub fn front_fixed_pos_at(&self, anchor: &Anchor, boundary: &Boundary) -> (f64, f64) {
+    self.front_fixed_pos_in(anchor.point(), boundary)
+  }
+
+  /// Set style of fixed positions and sizes at the anchor's point inside of the boundary
+  pub fn set_style_at<H: AsRef<HtmlElement>>(&self, anchor: &Anchor, elem: H, boundary: &Boundary) {
+    self.set_style_in(elem, anchor.point(), boundary)
+  }
+}
+
+impl PlacePosSize {
+
+  /// Place around the anchor for { position: fixed }, inside of the boundary.
+  pub fn placed_at(&self, anchor: &Anchor, boundary: &Boundary) -> Placed {
+    self.place(anchor.rect(), boundary.rect())
+  }
+
+  /// Set style of fixed positions and sizes around the anchor inside of the boundary. Return chosen placement.
+  pub fn set_style_at<H: AsRef<HtmlElement>>(&self, anchor: &Anchor, elem: H, boundary: &Boundary) -> Placed {
+    let placed = self.placed_at(anchor, boundary);
+    placed.set_style(elem);
+    placed
+  }
+}
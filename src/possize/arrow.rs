@@ -0,0 +1,131 @@
+//! Arrow of a floating element, pointing at its anchor
+//!
+//! When a tooltip is shifted or adjusted to stay inside of the boundary,
+//! an arrow at the middle of it no longer points at its anchor.
+//! [`Arrow`] computes which side of the floating element the arrow belongs on,
+//! and its offset along that side, so that it keeps pointing at the anchor's center.
+//!
+//! The offset is clamped by `padding`, to keep the arrow inside of the floating element's rounded corners.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! // floating element below the anchor, shifted to the right
+//! let anchor = Rect::new(0., 0., 20., 20.);
+//! let floating = Rect::new(4., 28., 100., 50.);
+//!
+//! let arrow = Arrow::new(anchor, floating, Side::Bottom, 8., 6.);
+//! assert_eq!(arrow.side, Side::Top);
+//! assert_eq!(arrow.offset, 6.); // anchor center(10) - floating left(4) - half arrow(4) = 2 => clamped by padding
+//!
+//! assert_eq!(Arrow::infer(anchor, floating, 8., 6.), arrow);
+//! ```
+
+use super::*;
+
+/// Arrow's side and offset on a floating element
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Arrow {
+  /// side of the floating element where the arrow belongs on: it faces the anchor.
+  pub side: Side,
+  /// arrow's front(left/top) position along the side, relative to the floating element's front.
+  pub offset: f64,
+  /// arrow's size along the side
+  pub size: f64,
+}
+
+impl Arrow {
+
+  /// Compute an arrow from the anchor's rect and the floating element's final rect.
+  ///
+  /// # Args
+  /// * placed_side: side of the anchor where the floating element is placed. (See [`Placement::side()`])
+  /// * size: arrow's size along the side
+  /// * padding: minimum distance between the arrow and the floating element's corners
+  pub fn new(anchor: Rect, floating: Rect, placed_side: Side, size: f64, padding: f64) -> Self {
+
+    let side = placed_side.opposite();
+    let cross_lateral = !side.lateral();
+
+    let center = anchor.front(cross_lateral) + anchor.size(cross_lateral)/2.;
+    let offset = center - floating.front(cross_lateral) - size/2.;
+
+    let min = padding;
+    let max = floating.size(cross_lateral) - padding - size;
+    let offset = if max<min {
+      // too small to keep the padding: just stay at the middle
+      (floating.size(cross_lateral) - size)/2.
+    } else {
+      offset.max(min).min(max)
+    };
+
+    Self { side, offset, size }
+  }
+
+  /// Compute an arrow, inferring the side from the rects.
+  /// It's useful when the placement is not known, like with [`AbsPosSize`].
+  pub fn infer(anchor: Rect, floating: Rect, size: f64, padding: f64) -> Self {
+    Self::new(anchor, floating, Self::placed_side(anchor, floating), size, padding)
+  }
+
+  /// Side of the anchor where the floating element is.
+  /// When they overlap, the side with larger distance between their centers is chosen.
+  pub fn placed_side(anchor: Rect, floating: Rect) -> Side {
+    if floating.bottom()<=anchor.top {
+      Side::Top
+    } else if floating.top>=anchor.bottom() {
+      Side::Bottom
+    } else if floating.right()<=anchor.left {
+      Side::Left
+    } else if floating.left>=anchor.right() {
+      Side::Right
+    } else {
+      let dx = (floating.left + floating.width/2.) - (anchor.left + anchor.width/2.);
+      let dy = (floating.top + floating.height/2.) - (anchor.top + anchor.height/2.);
+      if dx.abs()>dy.abs() {
+        if dx<0. { Side::Left } else { Side::Right }
+      } else if dy<0. {
+        Side::Top
+      } else {
+        Side::Bottom
+      }
+    }
+  }
+
+  /// Set style of the arrow element, which is supposed to be { position: absolute } inside of the floating element.
+  /// * `left`/`top`: offset along the side
+  /// * the side property(Ex. `top` for an arrow at the top side): minus half of the arrow's size, so as to stick out.
+  ///
+  /// Other side properties are removed, as the side might change on every placement.
+  pub fn set_style<H: AsRef<HtmlElement>>(&self, arrow: H) {
+    let style = arrow.as_ref().style();
+    for side in ["top", "right", "bottom", "left"] {
+      let _ = style.remove_property(side);
+    }
+    let (_, pos_prop) = size_pos_props(!self.side.lateral());
+    let _ = style.set_property(pos_prop, &format!("{:.2}px", self.offset));
+    let _ = style.set_property(self.side.as_str(), &format!("{:.2}px", -self.size/2.));
+  }
+}
+
+impl Placed {
+
+  /// Compute an arrow of the placed floating element. Check [`Arrow::new()`]
+  ///
+  /// `anchor` should be in the same coordinates with the placed rect.
+  pub fn arrow(&self, anchor: Rect, size: f64, padding: f64) -> Arrow {
+    Arrow::new(anchor, self.rect, self.placement.side(), size, padding)
+  }
+}
+
+impl AbsPosSize {
+
+  /// Compute an arrow of the floating element positioned by [`front_absolute_pos()`](Self::front_absolute_pos).
+  /// The side is inferred from the rects. Check [`Arrow::infer()`]
+  pub fn arrow<E: AsRef<Element>>(&self, ancestor: E, size: f64, padding: f64) -> Arrow {
+    let anchor = Rect::elem(ancestor.as_ref());
+    let (left, top) = self.front_absolute_pos(ancestor);
+    let floating = Rect::new(anchor.left + left, anchor.top + top, self.lateral.size, self.vertical.size);
+    Arrow::infer(anchor, floating, size, padding)
+  }
+}
@@ -0,0 +1,188 @@
+//! Auto-update positions of floating elements
+//!
+//! possize computes a position once, at the moment of being drawn. However the anchor moves when
+//! its scrolling container scrolls, the window resizes, or layout shifts change the anchor's or floating element's size.
+//!
+//! [`AutoUpdate`] re-runs an update closure whenever one of them happens:
+//! * `scroll` of any scrollable(clipping) ancestor of the anchor, and of the window
+//! * `resize` of the window
+//! * size changes of the anchor or the floating element (ResizeObserver)
+//!
+//! Listeners are removed when the handle is dropped, or [`AutoUpdate::clean()`] is called.
+//!
+//! Ready made updates: [`AbsPosSize::auto_update()`], [`FixedPosSize::auto_update()`],
+//! [`PlacePosSize::auto_update_fixed()`] and [`PlacePosSize::auto_update_absolute()`].
+//!
+//! For Sycamore, `AutoUpdate::init()` ties it to `on_cleanup`. (*feature `sycamore`*)
+
+use super::*;
+use std::{cell::Cell, rc::Rc};
+use web_sys::{EventTarget, ResizeObserver};
+
+/// Handle of auto-update listeners. Dropping it removes them.
+pub struct AutoUpdate {
+  targets: Vec<EventTarget>,
+  cb_update: Closure<dyn FnMut(Event)>,
+  observer: Option<ResizeObserver>,
+  _cb_observe: Closure<dyn FnMut(JsValue)>,
+}
+
+impl AutoUpdate {
+
+  /// Start auto-update. `update` runs once immediately, and then on every relevant change.
+  ///
+  /// # Args
+  /// * anchor: anchor(ancestor) element. Its scrollable ancestors are listened to, and its size is observed.
+  /// * floating: floating element. Its size is observed.
+  /// * update: closure re-computing positions. Ex. `move || possize.set_style(&ancestor, &elem)`
+  pub fn new(
+    anchor: Option<Element>,
+    floating: Option<Element>,
+    update: impl Fn() + 'static
+  ) -> Self {
+
+    let update = Rc::new(update);
+    update();
+
+    let update_ = update.clone();
+    let cb_update = Closure::<dyn FnMut(_)>::new(move |_: Event| update_());
+    // ResizeObserver fires once as soon as it observes, which is already done by the immediate update above.
+    let observed = Cell::new(false);
+    let cb_observe = Closure::<dyn FnMut(_)>::new(move |_: JsValue| {
+      if observed.replace(true) {
+        update();
+      }
+    });
+
+    // scroll & resize
+    let mut targets: Vec<EventTarget> = anchor.as_ref().map(|anchor| {
+      clipping_ancestors(anchor).into_iter().map(|x| x.into()).collect()
+    }).unwrap_or_default();
+    targets.push(gloo_utils::window().into());
+
+    for target in targets.iter() {
+      let _ = target.add_event_listener_with_callback("scroll", cb_update.as_ref().unchecked_ref());
+    }
+    let _ = gloo_utils::window().add_event_listener_with_callback("resize", cb_update.as_ref().unchecked_ref());
+
+    // size changes
+    let observer = ResizeObserver::new(cb_observe.as_ref().unchecked_ref()).ok();
+    if let Some(observer) = observer.as_ref() {
+      for elem in [anchor, floating].iter().flatten() {
+        observer.observe(elem);
+      }
+    }
+
+    Self { targets, cb_update, observer, _cb_observe: cb_observe }
+  }
+
+  /// Remove listeners. Same with dropping it.
+  pub fn clean(self) {}
+
+  /// Start auto-update with Sycamore's NodeRefs on mount, and clean it on cleanup.
+  /// Check [`new()`](Self::new)
+  ///
+  /// * update: closure taking the anchor and the floating element.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// # use sycamore::prelude::*;
+  /// #[component]
+  /// fn Tooltip<G: Html>() -> View<G> {
+  ///   let possize = AbsPosSize::new(
+  ///     (true, false, Sizon::abs(0.), 100., 10., 10.),
+  ///     (true, true, Sizon::abs(5.), 30., 10., 10.)
+  ///   );
+  ///   let (anchor_ref, floating_ref) = AutoUpdate::init(None, None, move |anchor, floating| {
+  ///     possize.set_style(anchor, floating);
+  ///   });
+  ///
+  ///   view! {
+  ///     div(ref=anchor_ref, style="position: relative;") {
+  ///       div(ref=floating_ref, style="position: absolute;") { "tooltip" }
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn init<G: GenericNode>(
+    anchor_ref: Option<NodeRef<G>>,
+    floating_ref: Option<NodeRef<G>>,
+    update: impl Fn(Element, HtmlElement) + Copy + 'static
+  ) -> (NodeRef<G>, NodeRef<G>) {
+
+    let anchor_ref: NodeRef<G> = anchor_ref.unwrap_or(create_node_ref());
+    let floating_ref: NodeRef<G> = floating_ref.unwrap_or(create_node_ref());
+
+    on_mount(move || {
+      let anchor = ref_get::<_, Element>(anchor_ref);
+      let floating = ref_get::<_, HtmlElement>(floating_ref);
+
+      let auto_update = Self::new(anchor, floating.map(|x| x.into()), move || {
+        if let (Some(anchor), Some(floating)) = (ref_get(anchor_ref), ref_get(floating_ref)) {
+          update(anchor, floating);
+        }
+      });
+      on_cleanup(move || auto_update.clean());
+    });
+
+    (anchor_ref, floating_ref)
+  }
+}
+
+impl Drop for AutoUpdate {
+  fn drop(&mut self) {
+    for target in self.targets.iter() {
+      let _ = target.remove_event_listener_with_callback("scroll", self.cb_update.as_ref().unchecked_ref());
+    }
+    let _ = gloo_utils::window().remove_event_listener_with_callback("resize", self.cb_update.as_ref().unchecked_ref());
+    if let Some(observer) = self.observer.as_ref() {
+      observer.disconnect();
+    }
+  }
+}
+
+
+impl AbsPosSize {
+
+  /// Keep setting style of absolute positions and sizes, while the ancestor moves. Check [`AutoUpdate`]
+  pub fn auto_update(&self, ancestor: Element, elem: HtmlElement) -> AutoUpdate {
+    let possize = *self;
+    AutoUpdate::new(Some(ancestor.clone()), Some(elem.clone().into()), move || {
+      possize.set_style(&ancestor, &elem);
+    })
+  }
+}
+
+impl FixedPosSize {
+
+  /// Keep setting style of fixed positions and sizes, while the window resizes. Check [`AutoUpdate`]
+  pub fn auto_update(&self, elem: HtmlElement, client_xy: (f64, f64)) -> AutoUpdate {
+    let possize = *self;
+    AutoUpdate::new(None, Some(elem.clone().into()), move || {
+      possize.set_style(&elem, client_xy);
+    })
+  }
+}
+
+impl PlacePosSize {
+
+  /// Keep setting style of fixed positions and sizes around the anchor, while it moves. Check [`AutoUpdate`]
+  pub fn auto_update_fixed(&self, anchor: Element, elem: HtmlElement) -> AutoUpdate {
+    let possize = self.clone();
+    AutoUpdate::new(Some(anchor.clone()), Some(elem.clone().into()), move || {
+      possize.set_fixed_style(&anchor, &elem);
+    })
+  }
+
+  /// Keep setting style of absolute positions and sizes around the ancestor, while it moves. Check [`AutoUpdate`]
+  pub fn auto_update_absolute(&self, ancestor: Element, elem: HtmlElement) -> AutoUpdate {
+    let possize = self.clone();
+    AutoUpdate::new(Some(ancestor.clone()), Some(elem.clone().into()), move || {
+      possize.set_absolute_style(&ancestor, &elem);
+    })
+  }
+}
@@ -0,0 +1,187 @@
+//! Collision boundaries of possize
+//!
+//! By default, possize keeps floating elements inside of browser(document)'s client area.
+//! However, a dropdown inside of a scrolling side panel would be clipped by the panel, not by the document.
+//!
+//! [`Boundary`] decides the area to stay inside of:
+//! * `Document`: browser's client area (default)
+//! * `Element`: an element's visible(client) area
+//! * `Clipping`: visible area of an element's clipping ancestors, those with `overflow` other than `visible`.
+//! * `Rect`: an explicit rect
+//! * `Padded`: another boundary, shrinked by padding. Use [`Boundary::padded()`]
+//!
+//! Margins of [`FixedUniPosSize`] and [`AbsUniPosSize`] still work as padding from the boundary.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let possize = FixedPosSize::new((100., 0., 0.), (150., 0., 0.));
+//! let boundary = Boundary::Rect(Rect::new(100., 100., 300., 300.));
+//! assert_eq!(possize.front_fixed_pos_in((350., 50.), &boundary), (300., 100.));
+//!
+//! let boundary = boundary.padded(10.);
+//! assert_eq!(possize.front_fixed_pos_in((350., 50.), &boundary), (290., 110.));
+//! ```
+
+use super::*;
+
+/// Area where a floating element should stay inside of
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Boundary {
+  /// browser(document)'s client area
+  #[default]
+  Document,
+  /// the element's visible(client) area, excluding its borders and scrollbars
+  Element(Element),
+  /// visible area of the element's clipping ancestors, intersected with the document's client area.
+  /// Pass an anchor element to find its clipping ancestors automatically.
+  Clipping(Element),
+  /// explicit rect in client coordinates
+  Rect(Rect),
+  /// the boundary shrinked by padding for every side
+  Padded(Box<Boundary>, f64),
+}
+
+impl Boundary {
+
+  /// Resolve the boundary into a rect in client coordinates
+  pub fn rect(&self) -> Rect {
+    match self {
+      Self::Document => Rect::document(),
+      Self::Element(elem) => get_client_rect(elem),
+      Self::Clipping(elem) => clipping_rect(elem),
+      Self::Rect(rect) => *rect,
+      Self::Padded(boundary, padding) => boundary.rect().inset(*padding),
+    }
+  }
+
+  /// Shrink the boundary by `padding` for every side
+  pub fn padded(self, padding: f64) -> Self {
+    Self::Padded(Box::new(self), padding)
+  }
+}
+
+/// Get an element's visible(client) area in client coordinates: padding box excluding scrollbars
+pub fn get_client_rect<E: AsRef<Element>>(elem: E) -> Rect {
+  let elem = elem.as_ref();
+  let rect = Rect::elem(elem);
+  Rect::new(
+    rect.left + elem.client_left() as f64,
+    rect.top + elem.client_top() as f64,
+    elem.client_width() as f64,
+    elem.client_height() as f64
+  )
+}
+
+/// Does the element clip its overflowing contents? (`overflow` other than `visible`)
+pub fn is_clipping<E: AsRef<Element>>(elem: E) -> bool {
+  let Some(style) = gloo_utils::window().get_computed_style(elem.as_ref()).ok().flatten() else { return false };
+  ["overflow-x", "overflow-y"].iter().any(|prop| {
+    style.get_property_value(prop).map(|value| value!="visible" && !value.is_empty()).unwrap_or(false)
+  })
+}
+
+/// Get the element's clipping ancestors, from the nearest one. The document element is excluded.
+pub fn clipping_ancestors<E: AsRef<Element>>(elem: E) -> Vec<Element> {
+  let doc = gloo_utils::document_element();
+  let mut ancestors = vec![];
+  let mut elem = elem.as_ref().parent_element();
+  while let Some(x) = elem {
+    if x==doc {
+      break;
+    }
+    if is_clipping(&x) {
+      ancestors.push(x.clone());
+    }
+    elem = x.parent_element();
+  }
+  ancestors
+}
+
+/// Get the visible area of the element's clipping ancestors, intersected with the document's client area.
+pub fn clipping_rect<E: AsRef<Element>>(elem: E) -> Rect {
+  clipping_ancestors(elem).iter().fold(Rect::document(), |rect, ancestor| {
+    rect.intersect(&get_client_rect(ancestor))
+  })
+}
+
+
+impl FixedPosSize {
+
+  /// Return adjusted front_fixed_pos(`left` and `top`) of an element, considering given position and boundary
+  pub fn front_fixed_pos_in(&self, (client_x, client_y): (f64, f64), boundary: &Boundary) -> (f64, f64) {
+    let rect = boundary.rect();
+
+    let fixed_left = self.lateral.front_fixed_pos(client_x - rect.left, rect.width) + rect.left;
+    let fixed_top = self.vertical.front_fixed_pos(client_y - rect.top, rect.height) + rect.top;
+    (fixed_left, fixed_top)
+  }
+
+  /// Set style of fixed positions and sizes, considering given boundary
+  pub fn set_style_in<H: AsRef<HtmlElement>>(&self, elem: H, client_xy: (f64, f64), boundary: &Boundary) {
+    let (fixed_left, fixed_top) = self.front_fixed_pos_in(client_xy, boundary);
+    PosStyle::new().set_style(elem, Rect::new(fixed_left, fixed_top, self.lateral.size, self.vertical.size), 0.);
+  }
+}
+
+impl AbsPosSize {
+
+  /// Return adjusted absolute front pos(`left` and `top`) of an element,
+  /// considering given ancestor's position and size, and boundary
+  pub fn front_absolute_pos_in<E: AsRef<Element>>(&self, ancestor: E, boundary: &Boundary) -> (f64, f64) {
+    let ancestor = Rect::elem(ancestor);
+    let (fixed_left, fixed_top) = self.front_fixed_pos_around(ancestor, boundary.rect());
+    (fixed_left-ancestor.left, fixed_top-ancestor.top)
+  }
+
+  /// Return adjusted front pos(`left` and `top`) of an element in client coordinates,
+  /// considering the anchor's rect and the boundary's rect. Both are in client coordinates.
+  pub fn front_fixed_pos_around(&self, anchor: Rect, boundary: Rect) -> (f64, f64) {
+    let fixed_left = self.lateral.front_absolute_to_fixed_pos(anchor.left - boundary.left, anchor.width, boundary.width) + boundary.left;
+    let fixed_top = self.vertical.front_absolute_to_fixed_pos(anchor.top - boundary.top, anchor.height, boundary.height) + boundary.top;
+    (fixed_left, fixed_top)
+  }
+
+  /// Set style of absolute positions and sizes, considering given boundary
+  pub fn set_style_in<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H, boundary: &Boundary) {
+    let (abs_left, abs_top) = self.front_absolute_pos_in(ancestor, boundary);
+    PosStyle::new().set_style(elem, Rect::new(abs_left, abs_top, self.lateral.size, self.vertical.size), 0.);
+  }
+}
+
+impl PlacePosSize {
+
+  /// Place around the anchor element for { position: fixed }, inside of given boundary.
+  /// Hide flags are detected with the anchor's clipping context.
+  pub fn fixed_placed_in<E: AsRef<Element>>(&self, anchor: E, boundary: &Boundary) -> Placed {
+    let anchor = anchor.as_ref();
+    let mut placed = self.place(Rect::elem(anchor), boundary.rect());
+    placed.hide = HideFlags::elem(anchor, placed.rect);
+    placed
+  }
+
+  /// Place around the ancestor element for { position: absolute }, inside of given boundary.
+  /// Returned rect is relative to the ancestor. Hide flags are detected with the ancestor's clipping context.
+  pub fn absolute_placed_in<E: AsRef<Element>>(&self, ancestor: E, boundary: &Boundary) -> Placed {
+    let elem = ancestor.as_ref();
+    let ancestor = Rect::elem(elem);
+    let mut placed = self.place(ancestor, boundary.rect());
+    placed.hide = HideFlags::elem(elem, placed.rect);
+    placed.rect = placed.rect.translate(-ancestor.left, -ancestor.top);
+    placed
+  }
+
+  /// Set style of fixed positions and sizes inside of given boundary. Return chosen placement.
+  pub fn set_fixed_style_in<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, anchor: E, elem: H, boundary: &Boundary) -> Placed {
+    let placed = self.fixed_placed_in(anchor, boundary);
+    placed.set_style(elem);
+    placed
+  }
+
+  /// Set style of absolute positions and sizes inside of given boundary. Return chosen placement.
+  pub fn set_absolute_style_in<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H, boundary: &Boundary) -> Placed {
+    let placed = self.absolute_placed_in(ancestor, boundary);
+    placed.set_style(elem);
+    placed
+  }
+}
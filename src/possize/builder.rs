@@ -0,0 +1,411 @@
+//! Builders of possize
+//!
+//! [`AbsPosSize::new()`] takes two 6-tuples of `(bool, bool, Sizon, f64, f64, f64)`,
+//! and it's easy to mix up the two booleans `front` and `outward`.
+//! Builders take named options with defaults instead:
+//! * [`AbsPosSizeBuilder`]: below the ancestor(`bottom_outside`), aligned with its left(`left_inside`) by default.
+//! * [`FixedPosSizeBuilder`]
+//!
+//! Sizes, margins and gaps are zero by default.
+//!
+//! Configs are also (de)serializable with serde, in named fields. [`AbsPosSizeConfig`] names sides like the builder
+//! (`"left-inside"`, `"bottom-outside"`(or `"below"`), ...) instead of `front` and `outward`.
+//! Missing fields take the builder's defaults, so that a config like `{"vertical": {"size": 120}}` works.
+//! Configs of former versions, with booleans `front` and `outward`, are still accepted.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let built = AbsPosSize::builder()
+//!   .right_outside()
+//!   .top_inside()
+//!   .gap(Sizon::abs(5.), Sizon::default())
+//!   .size(100., 150.)
+//!   .margin(10.)
+//!   .build();
+//!
+//! let possize = AbsPosSize::new(
+//!   (false, true, Sizon::abs(5.), 100., 10., 10.),
+//!   (true, false, Sizon::default(), 150., 10., 10.)
+//! );
+//! assert_eq!(built, possize);
+//! assert_eq!(built.size(), (100., 150.));
+//!
+//! // from JSON, and back
+//! let json = r#"{
+//!   "lateral": {"at": "right-outside", "gap": {"abs": 5}, "size": 100, "front_margin": 10, "rear_margin": 10},
+//!   "vertical": {"at": "top-inside", "size": 150, "front_margin": 10, "rear_margin": 10}
+//! }"#;
+//! let config: AbsPosSize = serde_json::from_str(json).unwrap();
+//! assert_eq!(config, built);
+//! assert_eq!(serde_json::from_str::<AbsPosSize>(&serde_json::to_string(&built).unwrap()).unwrap(), built);
+//!
+//! // missing fields are the builder's defaults
+//! let config: AbsPosSize = serde_json::from_str(r#"{"vertical": {"at": "below", "size": 120}}"#).unwrap();
+//! assert_eq!(config, AbsPosSize::builder().size(0., 120.).build());
+//! assert_eq!(AbsPosSize::default(), AbsPosSize::builder().build());
+//!
+//! let mut built = FixedPosSize::builder().size(160., 120.).vertical_margins(8., 16.).build();
+//! built.lateral_mut().size = 200.;
+//! assert_eq!(built, FixedPosSize::new((200., 0., 0.), (120., 8., 16.)));
+//! ```
+
+use super::*;
+
+/// Named sides, converted from and into `front` and `outward` of [`AbsUniPosSize`]
+pub trait AbsAt: Copy + Default {
+  /// (front, outward)
+  fn front_outward(self) -> (bool, bool);
+  fn from_front_outward(front: bool, outward: bool) -> Self;
+}
+
+/// Side of an [`AbsUniPosSize`] in either direction, relative to the ancestor
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AbsUniAt {
+  /// out of the ancestor's front: its rear meets the ancestor's front.
+  FrontOutside,
+  /// aligned with the ancestor's front inside
+  FrontInside,
+  /// aligned with the ancestor's rear inside
+  #[default]
+  RearInside,
+  /// out of the ancestor's rear: its front meets the ancestor's rear.
+  RearOutside,
+}
+
+impl AbsAt for AbsUniAt {
+  fn front_outward(self) -> (bool, bool) {
+    match self {
+      Self::FrontOutside => (true, true),
+      Self::FrontInside => (true, false),
+      Self::RearInside => (false, false),
+      Self::RearOutside => (false, true),
+    }
+  }
+
+  fn from_front_outward(front: bool, outward: bool) -> Self {
+    match (front, outward) {
+      (true, true) => Self::FrontOutside,
+      (true, false) => Self::FrontInside,
+      (false, false) => Self::RearInside,
+      (false, true) => Self::RearOutside,
+    }
+  }
+}
+
+/// Lateral side of an [`AbsPosSize`] relative to the ancestor. Same with the builder's methods.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AbsLateralAt {
+  LeftOutside,
+  #[default]
+  LeftInside,
+  RightInside,
+  RightOutside,
+}
+
+impl AbsAt for AbsLateralAt {
+  fn front_outward(self) -> (bool, bool) {
+    match self {
+      Self::LeftOutside => (true, true),
+      Self::LeftInside => (true, false),
+      Self::RightInside => (false, false),
+      Self::RightOutside => (false, true),
+    }
+  }
+
+  fn from_front_outward(front: bool, outward: bool) -> Self {
+    match (front, outward) {
+      (true, true) => Self::LeftOutside,
+      (true, false) => Self::LeftInside,
+      (false, false) => Self::RightInside,
+      (false, true) => Self::RightOutside,
+    }
+  }
+}
+
+/// Vertical side of an [`AbsPosSize`] relative to the ancestor. Same with the builder's methods.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AbsVerticalAt {
+  #[serde(alias = "above")]
+  TopOutside,
+  TopInside,
+  BottomInside,
+  #[default]
+  #[serde(alias = "below")]
+  BottomOutside,
+}
+
+impl AbsAt for AbsVerticalAt {
+  fn front_outward(self) -> (bool, bool) {
+    match self {
+      Self::TopOutside => (true, true),
+      Self::TopInside => (true, false),
+      Self::BottomInside => (false, false),
+      Self::BottomOutside => (false, true),
+    }
+  }
+
+  fn from_front_outward(front: bool, outward: bool) -> Self {
+    match (front, outward) {
+      (true, true) => Self::TopOutside,
+      (true, false) => Self::TopInside,
+      (false, false) => Self::BottomInside,
+      (false, true) => Self::BottomOutside,
+    }
+  }
+}
+
+/// Serde's config of [`AbsUniPosSize`], with a named side `at`
+///
+/// Former versions' `front` and `outward` are only read, and take precedence over `at` when either is given.
+/// Like before, a missing one of them is `false`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, bound(deserialize = "A: Deserialize<'de> + Default"))]
+pub struct AbsUniPosSizeConfig<A> {
+  pub at: A,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub front: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub outward: Option<bool>,
+  pub gap: Sizon,
+  pub size: f64,
+  pub front_margin: f64,
+  pub rear_margin: f64,
+}
+
+impl<A: AbsAt> From<AbsUniPosSizeConfig<A>> for AbsUniPosSize {
+  fn from(value: AbsUniPosSizeConfig<A>) -> Self {
+    let (front, outward) = match (value.front, value.outward) {
+      (None, None) => value.at.front_outward(),
+      (front, outward) => (front.unwrap_or(false), outward.unwrap_or(false)),
+    };
+    Self::new(front, outward, value.gap, value.size, value.front_margin, value.rear_margin)
+  }
+}
+
+impl<A: AbsAt> From<AbsUniPosSize> for AbsUniPosSizeConfig<A> {
+  fn from(value: AbsUniPosSize) -> Self {
+    Self {
+      at: A::from_front_outward(value.front, value.outward),
+      front: None,
+      outward: None,
+      gap: value.gap,
+      size: value.size,
+      front_margin: value.front_margin,
+      rear_margin: value.rear_margin,
+    }
+  }
+}
+
+/// Serde's config of [`AbsPosSize`]. Defaults are same with [`AbsPosSizeBuilder`]'s.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AbsPosSizeConfig {
+  pub lateral: AbsUniPosSizeConfig<AbsLateralAt>,
+  pub vertical: AbsUniPosSizeConfig<AbsVerticalAt>,
+}
+
+impl From<AbsPosSizeConfig> for AbsPosSize {
+  fn from(value: AbsPosSizeConfig) -> Self {
+    Self { lateral: value.lateral.into(), vertical: value.vertical.into() }
+  }
+}
+
+impl From<AbsPosSize> for AbsPosSizeConfig {
+  fn from(value: AbsPosSize) -> Self {
+    Self { lateral: value.lateral.into(), vertical: value.vertical.into() }
+  }
+}
+
+impl Default for AbsPosSize {
+  /// Below the ancestor, aligned with its left. Same with [`AbsPosSize::builder()`]
+  fn default() -> Self {
+    AbsPosSizeConfig::default().into()
+  }
+}
+
+
+/// Builder of [`AbsPosSize`]. Check the [module](crate::possize::builder) doc.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AbsPosSizeBuilder {
+  lateral: AbsUniPosSize,
+  vertical: AbsUniPosSize,
+}
+
+impl Default for AbsPosSizeBuilder {
+  fn default() -> Self {
+    let AbsPosSize { lateral, vertical } = AbsPosSize::default();
+    Self { lateral, vertical }
+  }
+}
+
+impl AbsPosSize {
+
+  /// Builder with named options. Check [`AbsPosSizeBuilder`]
+  pub fn builder() -> AbsPosSizeBuilder {
+    AbsPosSizeBuilder::default()
+  }
+}
+
+impl AbsPosSizeBuilder {
+
+  /// Lateral side relative to the ancestor
+  pub fn lateral_at(mut self, at: AbsLateralAt) -> Self {
+    (self.lateral.front, self.lateral.outward) = at.front_outward();
+    self
+  }
+
+  /// Vertical side relative to the ancestor
+  pub fn vertical_at(mut self, at: AbsVerticalAt) -> Self {
+    (self.vertical.front, self.vertical.outward) = at.front_outward();
+    self
+  }
+
+  /// At the left of the ancestor, spreading outwards: its right meets the ancestor's left.
+  pub fn left_outside(self) -> Self { self.lateral_at(AbsLateralAt::LeftOutside) }
+
+  /// Aligned with the ancestor's left, spreading inwards: its left meets the ancestor's left. (default)
+  pub fn left_inside(self) -> Self { self.lateral_at(AbsLateralAt::LeftInside) }
+
+  /// Aligned with the ancestor's right, spreading inwards: its right meets the ancestor's right.
+  pub fn right_inside(self) -> Self { self.lateral_at(AbsLateralAt::RightInside) }
+
+  /// At the right of the ancestor, spreading outwards: its left meets the ancestor's right.
+  pub fn right_outside(self) -> Self { self.lateral_at(AbsLateralAt::RightOutside) }
+
+  /// Above the ancestor, spreading outwards: its bottom meets the ancestor's top.
+  pub fn top_outside(self) -> Self { self.vertical_at(AbsVerticalAt::TopOutside) }
+
+  /// Aligned with the ancestor's top, spreading inwards: its top meets the ancestor's top.
+  pub fn top_inside(self) -> Self { self.vertical_at(AbsVerticalAt::TopInside) }
+
+  /// Aligned with the ancestor's bottom, spreading inwards: its bottom meets the ancestor's bottom.
+  pub fn bottom_inside(self) -> Self { self.vertical_at(AbsVerticalAt::BottomInside) }
+
+  /// Below the ancestor, spreading outwards: its top meets the ancestor's bottom. (default)
+  pub fn bottom_outside(self) -> Self { self.vertical_at(AbsVerticalAt::BottomOutside) }
+
+  /// Gaps between oneself and the ancestor's sides: (lateral, vertical)
+  pub fn gap(mut self, lateral: Sizon, vertical: Sizon) -> Self {
+    self.lateral.gap = lateral;
+    self.vertical.gap = vertical;
+    self
+  }
+
+  /// Size: (width, height)
+  pub fn size(mut self, width: f64, height: f64) -> Self {
+    self.lateral.size = width;
+    self.vertical.size = height;
+    self
+  }
+
+  /// Same margins for every side
+  pub fn margin(self, margin: f64) -> Self {
+    self.lateral_margins(margin, margin).vertical_margins(margin, margin)
+  }
+
+  /// Margins of left(front) and right(rear)
+  pub fn lateral_margins(mut self, front: f64, rear: f64) -> Self {
+    self.lateral.front_margin = front;
+    self.lateral.rear_margin = rear;
+    self
+  }
+
+  /// Margins of top(front) and bottom(rear)
+  pub fn vertical_margins(mut self, front: f64, rear: f64) -> Self {
+    self.vertical.front_margin = front;
+    self.vertical.rear_margin = rear;
+    self
+  }
+
+  pub fn build(self) -> AbsPosSize {
+    AbsPosSize { lateral: self.lateral, vertical: self.vertical }
+  }
+}
+
+
+/// Builder of [`FixedPosSize`]. Check the [module](crate::possize::builder) doc.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct FixedPosSizeBuilder {
+  lateral: FixedUniPosSize,
+  vertical: FixedUniPosSize,
+}
+
+impl FixedPosSize {
+
+  /// Builder with named options. Check [`FixedPosSizeBuilder`]
+  pub fn builder() -> FixedPosSizeBuilder {
+    FixedPosSizeBuilder::default()
+  }
+}
+
+impl FixedPosSizeBuilder {
+
+  /// Size: (width, height)
+  pub fn size(mut self, width: f64, height: f64) -> Self {
+    self.lateral.size = width;
+    self.vertical.size = height;
+    self
+  }
+
+  /// Same margins for every side
+  pub fn margin(self, margin: f64) -> Self {
+    self.lateral_margins(margin, margin).vertical_margins(margin, margin)
+  }
+
+  /// Margins of left(front) and right(rear)
+  pub fn lateral_margins(mut self, front: f64, rear: f64) -> Self {
+    self.lateral.front_margin = front;
+    self.lateral.rear_margin = rear;
+    self
+  }
+
+  /// Margins of top(front) and bottom(rear)
+  pub fn vertical_margins(mut self, front: f64, rear: f64) -> Self {
+    self.vertical.front_margin = front;
+    self.vertical.rear_margin = rear;
+    self
+  }
+
+  pub fn build(self) -> FixedPosSize {
+    FixedPosSize { lateral: self.lateral, vertical: self.vertical }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// `AbsPosSize` serialized by former versions, with `front` and `outward`
+  const LEGACY_ABS_POSSIZE: &str = r#"{
+    "lateral": {"front": false, "outward": true, "gap": {"abs": 5.0, "rel": null}, "size": 100.0, "front_margin": 10.0, "rear_margin": 10.0},
+    "vertical": {"front": true, "gap": {"abs": null, "rel": 0.1}, "size": 150.0, "front_margin": 0.0, "rear_margin": 8.0}
+  }"#;
+
+  #[test]
+  fn legacy_abs_possize_round_trip() {
+    let legacy: AbsPosSize = serde_json::from_str(LEGACY_ABS_POSSIZE).unwrap();
+    assert_eq!(legacy, AbsPosSize::new(
+      (false, true, Sizon::abs(5.), 100., 10., 10.),
+      (true, false, Sizon::rel(0.1), 150., 0., 8.)
+    ));
+
+    let json = serde_json::to_value(legacy).unwrap();
+    assert_eq!(json["lateral"]["at"], "right-outside");
+    assert_eq!(json["vertical"]["at"], "top-inside");
+    assert!(json["lateral"].get("front").is_none() && json["lateral"].get("outward").is_none());
+    assert_eq!(serde_json::from_value::<AbsPosSize>(json).unwrap(), legacy);
+  }
+
+  #[test]
+  fn legacy_abs_uni_possize() {
+    let legacy: AbsUniPosSize = serde_json::from_str(r#"{"outward": true, "size": 40.0}"#).unwrap();
+    assert_eq!(legacy, AbsUniPosSize::new(false, true, Sizon::default(), 40., 0., 0.));
+    let json = serde_json::to_string(&legacy).unwrap();
+    assert_eq!(serde_json::from_str::<AbsUniPosSize>(&json).unwrap(), legacy);
+  }
+}
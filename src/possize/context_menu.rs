@@ -0,0 +1,186 @@
+//! Context menu controller
+//!
+//! [`FixedPosSize`] positions a context menu, while a context menu needs more wiring:
+//! * open on `contextmenu` event, preventing the browser's default menu
+//! * dismiss on outside click or `Escape`
+//! * return focus to the previously focused element on dismiss
+//! * navigate between items with arrow keys(`ArrowUp`, `ArrowDown`, `Home`, `End`)
+//!
+//! `ContextMenu::init_context_menu()` does them all for Sycamore. (*feature `sycamore`*)
+//!
+//! Items are found by [`ContextMenu::ITEM_SELECTOR`] inside of the menu element. Make them focusable, like `tabindex="-1"`.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! assert_eq!(ContextMenu::next_index(3, None, "ArrowDown"), Some(0));
+//! assert_eq!(ContextMenu::next_index(3, Some(2), "ArrowDown"), Some(0));
+//! assert_eq!(ContextMenu::next_index(3, Some(0), "ArrowUp"), Some(2));
+//! assert_eq!(ContextMenu::next_index(3, Some(1), "End"), Some(2));
+//! assert_eq!(ContextMenu::next_index(3, Some(1), "Enter"), None);
+//! ```
+
+use super::*;
+
+/// State of a context menu: is it open, and where was it opened at?
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContextMenu {
+  /// is the menu open?
+  pub open: bool,
+  /// client position of the `contextmenu` event
+  pub client_xy: (f64, f64),
+}
+
+impl ContextMenu {
+
+  /// css selector of menu items
+  pub const ITEM_SELECTOR: &'static str = "[role=\"menuitem\"]:not([aria-disabled=\"true\"])";
+
+  /// Index of the item to focus by the key, among `len` items. Navigation wraps around.
+  /// Return None when the key is not for navigation, or there is no item.
+  pub fn next_index(len: usize, current: Option<usize>, key: &str) -> Option<usize> {
+    if len==0 {
+      return None;
+    }
+    match (key, current) {
+      ("ArrowDown", Some(i)) => Some((i+1) % len),
+      ("ArrowDown", None) | ("Home", _) => Some(0),
+      ("ArrowUp", Some(i)) => Some((i+len-1) % len),
+      ("ArrowUp", None) | ("End", _) => Some(len-1),
+      _ => None,
+    }
+  }
+
+  /// Get focusable items of the menu element
+  pub fn items<E: AsRef<Element>>(menu: E) -> Vec<HtmlElement> {
+    let Ok(nodes) = menu.as_ref().query_selector_all(Self::ITEM_SELECTOR) else { return vec![] };
+    (0..nodes.length()).filter_map(|i| nodes.item(i)).filter_map(|x| x.dyn_into::<HtmlElement>().ok()).collect()
+  }
+
+  /// Move focus between the menu's items by the key. Return true when the key is handled.
+  pub fn navigate<E: AsRef<Element>>(menu: E, key: &str) -> bool {
+    let items = Self::items(menu);
+    let active = gloo_utils::document().active_element();
+    let current = active.and_then(|active| items.iter().position(|x| AsRef::<Element>::as_ref(x)==&active));
+    match Self::next_index(items.len(), current, key) {
+      Some(i) => {
+        let _ = items[i].focus();
+        true
+      },
+      None => false,
+    }
+  }
+
+  /// Context menu controller for Sycamore.
+  ///
+  /// # Args
+  /// * target_ref: NodeRef of the element where `contextmenu` event opens the menu
+  /// * menu_ref: NodeRef of the menu element, which is supposed to be { position: fixed }
+  /// * possize: position and size of the menu
+  ///
+  /// # Outputs
+  /// (target_ref, menu_ref, signal of the menu's state)
+  ///
+  /// The menu's visibility is up to the signal's `open`: the controller only positions it and handles focus.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// # use sycamore::prelude::*;
+  /// #[component]
+  /// fn Component<G: Html>() -> View<G> {
+  ///   let possize = FixedPosSize::new((160., 8., 8.), (120., 8., 8.));
+  ///   let (target_ref, menu_ref, state) = ContextMenu::init_context_menu(None, None, possize);
+  ///
+  ///   view! {
+  ///     div(ref=target_ref) { "right click here" }
+  ///     div(ref=menu_ref, role="menu", style="position: fixed;") {
+  ///       div(role="menuitem", tabindex="-1") { "copy" }
+  ///       div(role="menuitem", tabindex="-1") { "paste" }
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn init_context_menu<G: GenericNode>(
+    target_ref: Option<NodeRef<G>>,
+    menu_ref: Option<NodeRef<G>>,
+    possize: FixedPosSize
+  ) -> (NodeRef<G>, NodeRef<G>, Signal<Self>) {
+
+    let target_ref: NodeRef<G> = target_ref.unwrap_or(create_node_ref());
+    let menu_ref: NodeRef<G> = menu_ref.unwrap_or(create_node_ref());
+    let state = create_signal(Self::default());
+    let return_focus = create_signal(None::<HtmlElement>);
+
+    let cb_contextmenu = Closure::<dyn FnMut(_)>::new(move |e: web_sys::MouseEvent| {
+      e.prevent_default();
+      if !state.with(|x| x.open) {
+        let active = gloo_utils::document().active_element().and_then(|x| x.dyn_into::<HtmlElement>().ok());
+        return_focus.set(active);
+      }
+      state.set(Self { open: true, client_xy: (e.client_x() as f64, e.client_y() as f64) });
+    });
+
+    let cb_pointerdown = Closure::<dyn FnMut(_)>::new(move |e: PointerEvent| {
+      if !state.with(|x| x.open) {
+        return;
+      }
+      let target = e.target().and_then(|x| x.dyn_into::<web_sys::Node>().ok());
+      let inside = ref_get::<_, Element>(menu_ref).map(|menu| menu.contains(target.as_ref())).unwrap_or(false);
+      if !inside {
+        state.set(Self { open: false, ..state.get() });
+      }
+    });
+
+    let cb_keydown = Closure::<dyn FnMut(_)>::new(move |e: web_sys::KeyboardEvent| {
+      if !state.with(|x| x.open) {
+        return;
+      }
+      let key = e.key();
+      if key=="Escape" {
+        e.prevent_default();
+        state.set(Self { open: false, ..state.get() });
+      } else if let Some(menu) = ref_get::<_, Element>(menu_ref) {
+        if Self::navigate(menu, &key) {
+          e.prevent_default();
+        }
+      }
+    });
+
+    on_mount(move || {
+      create_effect(on(state, move || {
+        let Self { open, client_xy } = state.get();
+        if open {
+          if let Some(menu) = ref_get::<_, HtmlElement>(menu_ref) {
+            possize.set_style(&menu, client_xy);
+            let _ = Self::items(&menu).first().map(|x| x.focus());
+          }
+        } else if let Some(x) = return_focus.get_clone() {
+          let _ = x.focus();
+          return_focus.set(None);
+        }
+      }));
+
+      let document = gloo_utils::document();
+      document.add_event_listener_with_callback("pointerdown", cb_pointerdown.as_ref().unchecked_ref()).unwrap_throw();
+      document.add_event_listener_with_callback("keydown", cb_keydown.as_ref().unchecked_ref()).unwrap_throw();
+      let target = ref_get::<_, EventTarget>(target_ref);
+      if let Some(target) = target.as_ref() {
+        target.add_event_listener_with_callback("contextmenu", cb_contextmenu.as_ref().unchecked_ref()).unwrap_throw();
+      }
+
+      on_cleanup(move || {
+        document.remove_event_listener_with_callback("pointerdown", cb_pointerdown.as_ref().unchecked_ref()).unwrap_throw();
+        document.remove_event_listener_with_callback("keydown", cb_keydown.as_ref().unchecked_ref()).unwrap_throw();
+        if let Some(target) = target {
+          target.remove_event_listener_with_callback("contextmenu", cb_contextmenu.as_ref().unchecked_ref()).unwrap_throw();
+        }
+      });
+    });
+
+    (target_ref, menu_ref, state)
+  }
+}
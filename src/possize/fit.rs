@@ -0,0 +1,180 @@
+//! Fit floating elements into available space ("size" mode)
+//!
+//! When neither the preferred side nor the opposite side can hold the floating element's size,
+//! adjusting its position only pushes it against the edge, and it still goes over the boundary.
+//! (Ex. a long dropdown list near the bottom of a small screen)
+//!
+//! Instead, fit it into the available space of the chosen side:
+//! * `available_size_*()`: report the available space
+//! * `fit_*()`: shrink the size to the available space, and position it
+//! * `set_fitted_style_*()`: set style of the fitted one.
+//!   With `max_size` on, `max-width`/`max-height` are written instead of `width`/`height`,
+//!   so that the element keeps its natural size when it fits, and gets scrollable with `overflow: auto` when it doesn't.
+//!
+//! [`PlacePosSize`] has field `fit` for this mode, and [`Placed`] always reports `available`.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! // 300px long dropdown below the anchor
+//! let possize = AbsPosSize::new(
+//!   (true, false, Sizon::abs(0.), 100., 10., 10.),
+//!   (false, true, Sizon::abs(5.), 300., 10., 10.)
+//! );
+//! let anchor = Rect::new(100., 250., 100., 30.);
+//! let boundary = Rect::new(0., 0., 800., 500.);
+//!
+//! // not enough space at both sides: it stays at the bottom, and gets shrinked into 205px.
+//! assert_eq!(possize.available_size_around(anchor, boundary), (690., 205.));
+//! assert_eq!(possize.fit_around(anchor, boundary), Rect::new(100., 285., 100., 205.));
+//! ```
+
+use super::*;
+
+/// Set style of maximum sizes: `max-width`, `max-height`
+pub fn set_max_size_style<H: AsRef<HtmlElement>>(elem: H, (width, height): (f64, f64)) {
+  let style = elem.as_ref().style();
+  let _ = style.set_property("max-width", &format!("{:.2}px", width));
+  let _ = style.set_property("max-height", &format!("{:.2}px", height));
+}
+
+
+impl FixedUniPosSize {
+
+  /// Return adjusted front_fixed_pos and fitted size: (front_fixed_pos, size)
+  pub fn fit(&self, client_pos: f64, doc_size: f64) -> (f64, f64) {
+    let fitted = Self { size: self.size.min(self.max_size(doc_size)), ..*self };
+    (fitted.front_fixed_pos(client_pos, doc_size), fitted.size)
+  }
+}
+
+impl AbsUniPosSize {
+
+  /// Return adjusted front_fixed_pos and fitted size: (front_fixed_pos, size).
+  /// The size is shrinked to the [`available_size()`](Self::available_size) of the chosen side.
+  pub fn fit(&self, ancestor_front_pos: f64, ancestor_size: f64, doc_size: f64) -> (f64, f64) {
+
+    let opposite = self.is_opposite_chosen(ancestor_front_pos, ancestor_size, doc_size);
+    let available = self.available_size(ancestor_front_pos, ancestor_size, doc_size);
+    let fitted = Self { size: self.size.min(available), ..*self };
+
+    let mut front_fixed_pos = fitted.raw_front_fixed_pos(opposite, ancestor_front_pos, ancestor_size);
+    if fitted.is_over(front_fixed_pos, doc_size) {
+      fitted.adjust_front_pos(&mut front_fixed_pos, doc_size);
+    }
+    (front_fixed_pos, fitted.size)
+  }
+}
+
+
+impl FixedPosSize {
+
+  /// Available size(width, height) inside of the boundary
+  pub fn available_size_in(&self, boundary: &Boundary) -> (f64, f64) {
+    let rect = boundary.rect();
+    (self.lateral.max_size(rect.width), self.vertical.max_size(rect.height))
+  }
+
+  /// Return fitted rect in client coordinates, at given position inside of the boundary
+  pub fn fit_in(&self, (client_x, client_y): (f64, f64), boundary: &Boundary) -> Rect {
+    let rect = boundary.rect();
+    let (left, width) = self.lateral.fit(client_x - rect.left, rect.width);
+    let (top, height) = self.vertical.fit(client_y - rect.top, rect.height);
+    Rect::new(left + rect.left, top + rect.top, width, height)
+  }
+
+  /// Set style of fitted positions and sizes inside of the boundary.
+  /// With `max_size` on, `max-width`/`max-height` are written instead of `width`/`height`.
+  pub fn set_fitted_style_in<H: AsRef<HtmlElement>>(&self, elem: H, client_xy: (f64, f64), boundary: &Boundary, max_size: bool) {
+    let rect = self.fit_in(client_xy, boundary);
+    set_fitted_style(elem, rect, max_size);
+  }
+}
+
+impl AbsPosSize {
+
+  /// Available size(width, height) on the chosen sides around the anchor, inside of the boundary.
+  /// Both rects are in client coordinates.
+  pub fn available_size_around(&self, anchor: Rect, boundary: Rect) -> (f64, f64) {
+    (
+      self.lateral.available_size(anchor.left - boundary.left, anchor.width, boundary.width),
+      self.vertical.available_size(anchor.top - boundary.top, anchor.height, boundary.height)
+    )
+  }
+
+  /// Return fitted rect in client coordinates, around the anchor inside of the boundary.
+  /// Both rects are in client coordinates.
+  pub fn fit_around(&self, anchor: Rect, boundary: Rect) -> Rect {
+    let (left, width) = self.lateral.fit(anchor.left - boundary.left, anchor.width, boundary.width);
+    let (top, height) = self.vertical.fit(anchor.top - boundary.top, anchor.height, boundary.height);
+    Rect::new(left + boundary.left, top + boundary.top, width, height)
+  }
+
+  /// Available size(width, height) on the chosen sides around the ancestor, inside of the boundary
+  pub fn available_size_in<E: AsRef<Element>>(&self, ancestor: E, boundary: &Boundary) -> (f64, f64) {
+    self.available_size_around(Rect::elem(ancestor), boundary.rect())
+  }
+
+  /// Set style of fitted absolute positions and sizes around the ancestor, inside of the boundary.
+  /// With `max_size` on, `max-width`/`max-height` are written instead of `width`/`height`.
+  pub fn set_fitted_style_in<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H, boundary: &Boundary, max_size: bool) {
+    let ancestor = Rect::elem(ancestor);
+    let rect = self.fit_around(ancestor, boundary.rect());
+    set_fitted_style(elem, rect.translate(-ancestor.left, -ancestor.top), max_size);
+  }
+}
+
+/// Set style of `top`, `left` and sizes or maximum sizes
+fn set_fitted_style<H: AsRef<HtmlElement>>(elem: H, rect: Rect, max_size: bool) {
+  if max_size {
+    PosStyle::new().size(false).set_style(elem.as_ref(), rect, 0.);
+    set_max_size_style(elem, (rect.width, rect.height));
+  } else {
+    PosStyle::new().set_style(elem, rect, 0.);
+  }
+}
+
+
+impl Placed {
+
+  /// Set style of maximum sizes with the available space: `max-width`, `max-height`
+  pub fn set_max_size_style<H: AsRef<HtmlElement>>(&self, elem: H) {
+    set_max_size_style(elem, self.available);
+  }
+}
+
+impl PlacePosSize {
+
+  /// Available size(width, height) at given placement around the anchor, inside of the boundary.
+  /// * main axis: space between the anchor's side(with gap) and the boundary's edge(with margin)
+  /// * cross axis: the boundary's size excluding margins
+  pub fn available_size(&self, placement: Placement, anchor: Rect, boundary: Rect) -> (f64, f64) {
+    let side = placement.side();
+    let main_lateral = side.lateral();
+    let gap = self.gap.to_abs(anchor.size(main_lateral)).unwrap_or(0.);
+    let main = self.uni(main_lateral);
+
+    let main_available = if side.front() {
+      anchor.front(main_lateral) - gap - boundary.front(main_lateral) - main.front_margin
+    } else {
+      boundary.rear(main_lateral) - main.rear_margin - anchor.rear(main_lateral) - gap
+    }.max(0.);
+    let cross_available = self.uni(!main_lateral).max_size(boundary.size(!main_lateral));
+
+    if main_lateral { (main_available, cross_available) } else { (cross_available, main_available) }
+  }
+
+  /// Shrink the raw rect of given placement into the available size. Main axis keeps sticking to the anchor.
+  pub(super) fn fit_rect(&self, placement: Placement, mut rect: Rect, (width, height): (f64, f64)) -> Rect {
+    let side = placement.side();
+    if rect.width>width {
+      if side==Side::Left { rect.left += rect.width - width; }
+      rect.width = width;
+    }
+    if rect.height>height {
+      if side==Side::Top { rect.top += rect.height - height; }
+      rect.height = height;
+    }
+    rect
+  }
+}
@@ -0,0 +1,177 @@
+//! Follow-cursor mode
+//!
+//! A value readout of charts or timelines follows the pointer while it is over the anchor.
+//! [`FollowCursor`] places the floating element at an offset from the pointer, kept in view by
+//! [`UniPosSize::adjust_front_pos()`].
+//!
+//! Movement can be restricted to one axis with [`FollowAxis`]. (Ex. only x for timeline scrubbing)
+//! Then the other axis stays at the anchor's front(left/top) plus the offset.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let anchor = Rect::new(100., 300., 600., 80.);
+//! let boundary = Rect::new(0., 0., 800., 600.);
+//!
+//! let follow = FollowCursor::new((12., 12.), FollowAxis::Both, (100., 0., 0.), (40., 0., 0.));
+//! assert_eq!(follow.front_fixed_pos((400., 340.), anchor, boundary), (412., 352.));
+//! // kept in view
+//! assert_eq!(follow.front_fixed_pos((750., 340.), anchor, boundary), (700., 352.));
+//!
+//! // timeline scrubbing: above the anchor, moving only along x
+//! let follow = FollowCursor::new((0., -44.), FollowAxis::X, (100., 0., 0.), (40., 0., 0.));
+//! assert_eq!(follow.front_fixed_pos((400., 340.), anchor, boundary), (400., 256.));
+//! ```
+
+use super::*;
+
+/// Axis along which a floating element follows the pointer
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FollowAxis {
+  #[default]
+  Both,
+  X,
+  Y,
+}
+
+/// # Follow-cursor Positioned Size
+///
+/// A relevant element is supposed to have style { position: fixed; }
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FollowCursor {
+  /// offset from the pointer: (x, y)
+  pub offset: (f64, f64),
+  /// axis to follow the pointer
+  pub axis: FollowAxis,
+  /// how to write the position. By default, only `left`/`top` are written.
+  pub pos_style: PosStyle,
+  /// lateral size and margins
+  pub lateral: FixedUniPosSize,
+  /// vertical size and margins
+  pub vertical: FixedUniPosSize,
+}
+
+impl FollowCursor {
+
+  /// # Args
+  /// offset: (f64, f64), axis: FollowAxis,
+  ///
+  /// (size_x, front_margin_x, rear_margin_x): (f64, f64, f64),
+  ///
+  /// (size_y, front_margin_y, rear_margin_y): (f64, f64, f64)
+  pub fn new(
+    offset: (f64, f64),
+    axis: FollowAxis,
+    (size_x, front_margin_x, rear_margin_x): (f64, f64, f64),
+    (size_y, front_margin_y, rear_margin_y): (f64, f64, f64),
+  ) -> Self {
+    Self {
+      offset,
+      axis,
+      pos_style: PosStyle::new().size(false),
+      lateral: FixedUniPosSize::new(size_x, front_margin_x, rear_margin_x),
+      vertical: FixedUniPosSize::new(size_y, front_margin_y, rear_margin_y),
+    }
+  }
+
+  /// Return adjusted front_fixed_pos(`left` and `top`) for the pointer over the anchor, inside of the boundary.
+  /// All are in client coordinates.
+  pub fn front_fixed_pos(&self, (x, y): (f64, f64), anchor: Rect, boundary: Rect) -> (f64, f64) {
+    let (x, y) = match self.axis {
+      FollowAxis::Both => (x, y),
+      FollowAxis::X => (x, anchor.top),
+      FollowAxis::Y => (anchor.left, y),
+    };
+
+    let mut left = x + self.offset.0 - boundary.left;
+    self.lateral.adjust_front_pos(&mut left, boundary.width);
+    let mut top = y + self.offset.1 - boundary.top;
+    self.vertical.adjust_front_pos(&mut top, boundary.height);
+
+    (left + boundary.left, top + boundary.top)
+  }
+
+  /// Set style of the floating element for the pointer over the anchor, inside of the boundary
+  pub fn set_style_in<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, anchor: E, elem: H, point: (f64, f64), boundary: &Boundary) {
+    let (left, top) = self.front_fixed_pos(point, Rect::elem(anchor), boundary.rect());
+    let rect = Rect::new(left, top, self.lateral.size, self.vertical.size);
+    self.pos_style.set_style(elem, rect, Rect::document().width);
+  }
+
+  /// `pointermove` listener of the anchor, setting style of the floating element.
+  /// Browser's client area is the boundary.
+  ///
+  /// # Args
+  /// * anchor, floating: anchor and floating element
+  /// * get_anchor, get_floating: how to get elements from `anchor` and `floating`
+  pub fn listener<X: Copy + 'static, E: AsRef<Element>, H: AsRef<HtmlElement>>(
+    &self,
+    anchor: X,
+    floating: X,
+    get_anchor: impl Fn(X) -> Option<E> + 'static,
+    get_floating: impl Fn(X) -> Option<H> + 'static
+  ) -> Closure<dyn FnMut(PointerEvent)> {
+    let follow = *self;
+    Closure::<dyn FnMut(_)>::new(move |e: PointerEvent| {
+      if let (Some(anchor), Some(floating)) = (get_anchor(anchor), get_floating(floating)) {
+        let point = (e.client_x() as f64, e.client_y() as f64);
+        follow.set_style_in(anchor, floating, point, &Boundary::Document);
+      }
+    })
+  }
+
+  /// Follow-cursor for Sycamore. Listeners are added to the anchor on mount, and removed on cleanup.
+  ///
+  /// # Outputs
+  /// (anchor_ref, floating_ref, signal of the pointer being over the anchor)
+  ///
+  /// The floating element's visibility is up to the signal.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// # use sycamore::prelude::*;
+  /// #[component]
+  /// fn Timeline<G: Html>() -> View<G> {
+  ///   let follow = FollowCursor::new((0., -32.), FollowAxis::X, (80., 4., 4.), (24., 4., 4.));
+  ///   let (anchor_ref, floating_ref, _hovering) = follow.init_follow_cursor(None, None);
+  ///
+  ///   view! {
+  ///     div(ref=anchor_ref) { "timeline" }
+  ///     div(ref=floating_ref, style="position: fixed;") { "00:00" }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn init_follow_cursor<G: GenericNode>(
+    &self,
+    anchor_ref: Option<NodeRef<G>>,
+    floating_ref: Option<NodeRef<G>>
+  ) -> (NodeRef<G>, NodeRef<G>, Signal<bool>) {
+
+    let anchor_ref: NodeRef<G> = anchor_ref.unwrap_or(create_node_ref());
+    let floating_ref: NodeRef<G> = floating_ref.unwrap_or(create_node_ref());
+    let hovering = create_signal(false);
+
+    let cb_move = self.listener(anchor_ref, floating_ref, ref_get::<_, Element>, ref_get::<_, HtmlElement>);
+    let cb_enter = Closure::<dyn FnMut(_)>::new(move |_: PointerEvent| hovering.set(true));
+    let cb_leave = Closure::<dyn FnMut(_)>::new(move |_: PointerEvent| hovering.set(false));
+
+    on_mount(move || {
+      if let Some(anchor) = ref_get::<_, EventTarget>(anchor_ref) {
+        anchor.add_event_listener_with_callback("pointermove", cb_move.as_ref().unchecked_ref()).unwrap_throw();
+        anchor.add_event_listener_with_callback("pointerenter", cb_enter.as_ref().unchecked_ref()).unwrap_throw();
+        anchor.add_event_listener_with_callback("pointerleave", cb_leave.as_ref().unchecked_ref()).unwrap_throw();
+        on_cleanup(move || {
+          anchor.remove_event_listener_with_callback("pointermove", cb_move.as_ref().unchecked_ref()).unwrap_throw();
+          anchor.remove_event_listener_with_callback("pointerenter", cb_enter.as_ref().unchecked_ref()).unwrap_throw();
+          anchor.remove_event_listener_with_callback("pointerleave", cb_leave.as_ref().unchecked_ref()).unwrap_throw();
+        });
+      }
+    });
+
+    (anchor_ref, floating_ref, hovering)
+  }
+}
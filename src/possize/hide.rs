@@ -0,0 +1,123 @@
+//! Reference-hidden and escaped detection
+//!
+//! When an anchor scrolls out of its scrolling container, a tooltip positioned around it keeps floating over unrelated contents.
+//! [`HideFlags`] reports alongside the computed position:
+//! * `reference_hidden`: the anchor is fully clipped by its scroll containers, or out of the viewport.
+//! * `escaped`: the floating element is fully out of its anchor's clipping context.
+//!
+//! The clipping context is [`clipping_rect()`] of the anchor element: its clipping ancestors' visible area inside of the viewport.
+//! For rects without elements, the boundary is taken as the clipping context.
+//!
+//! * [`Placed`] has field `hide`.
+//! * [`AbsPosSize::detect_in()`] and [`FixedPosSize::detect_in()`] return the flags with positions.
+//! * [`HideFlags::set_style()`] hides the floating element with `visibility: hidden`.
+//! * Opt in [`PosStyle`]'s field `hide`, and `set_style_with` of possize hides it along with positions.
+//!   Ex. `AutoUpdate::new(.., move || possize.fixed_placed_in(&anchor, &boundary).set_style_with(&elem, &PosStyle::new().hide(true), width))`
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let clipping = Rect::new(0., 100., 300., 400.);
+//!
+//! // anchor scrolled up above the container
+//! let anchor = Rect::new(20., 40., 100., 30.);
+//! let floating = Rect::new(20., 74., 100., 40.);
+//! let hide = HideFlags::new(anchor, floating, clipping);
+//! assert_eq!((hide.reference_hidden, hide.escaped), (true, false));
+//!
+//! let possize = PlacePosSize::new(Placement::Top, Sizon::abs(4.), (100., 0., 0.), (40., 0., 0.));
+//! let placed = possize.place(anchor, clipping);
+//! assert!(placed.hide.reference_hidden);
+//!
+//! // hidden along with its position, when opted in
+//! let properties = PosStyle::new().hide(true).properties(placed.rect, 300., Some(placed.hide));
+//! assert!(properties.contains(&("visibility", Some("hidden".to_string()))));
+//! let properties = PosStyle::new().properties(placed.rect, 300., Some(placed.hide));
+//! assert!(properties.iter().all(|(property, _)| *property!="visibility"));
+//! ```
+
+use super::*;
+
+/// Flags to hide a floating element
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HideFlags {
+  /// the anchor is fully clipped or out of the viewport
+  pub reference_hidden: bool,
+  /// the floating element is fully out of the anchor's clipping context
+  pub escaped: bool,
+}
+
+/// Is the rect fully out of the clipping rect? Zero sized rects on its edges are not.
+fn is_clipped(rect: Rect, clipping: Rect) -> bool {
+  [true, false].into_iter().any(|lateral| {
+    let (front, rear) = (rect.front(lateral), rect.rear(lateral));
+    let (clip_front, clip_rear) = (clipping.front(lateral), clipping.rear(lateral));
+    if rect.size(lateral)>0. {
+      rear<=clip_front || front>=clip_rear
+    } else {
+      front<clip_front || front>clip_rear
+    }
+  })
+}
+
+impl HideFlags {
+
+  /// Detect from rects of the anchor, the floating element and the anchor's clipping context.
+  /// All are in the same coordinates.
+  pub fn new(anchor: Rect, floating: Rect, clipping: Rect) -> Self {
+    Self {
+      reference_hidden: is_clipped(anchor, clipping),
+      escaped: is_clipped(floating, clipping),
+    }
+  }
+
+  /// Detect with the anchor element's clipping context. `floating` is in client coordinates.
+  pub fn elem<E: AsRef<Element>>(anchor: E, floating: Rect) -> Self {
+    let anchor = anchor.as_ref();
+    Self::new(Rect::elem(anchor), floating, clipping_rect(anchor))
+  }
+
+  /// Should the floating element be hidden? Either of flags is on.
+  pub fn hidden(&self) -> bool {
+    self.reference_hidden || self.escaped
+  }
+
+  /// Value of `visibility`: `hidden` when [`hidden()`](Self::hidden), otherwise none(to be removed).
+  pub fn visibility(&self) -> Option<&'static str> {
+    if self.hidden() { Some("hidden") } else { None }
+  }
+
+  /// Set style of `visibility`. Check [`visibility()`](Self::visibility)
+  pub fn set_style<H: AsRef<HtmlElement>>(&self, elem: H) {
+    let style = elem.as_ref().style();
+    let _ = match self.visibility() {
+      Some(value) => style.set_property("visibility", value),
+      None => style.remove_property("visibility").map(|_| ()),
+    };
+  }
+}
+
+
+impl AbsPosSize {
+
+  /// Return adjusted absolute front pos(`left` and `top`) with hide flags, considering given ancestor and boundary
+  pub fn detect_in<E: AsRef<Element>>(&self, ancestor: E, boundary: &Boundary) -> ((f64, f64), HideFlags) {
+    let ancestor = ancestor.as_ref();
+    let anchor = Rect::elem(ancestor);
+    let (left, top) = self.front_fixed_pos_around(anchor, boundary.rect());
+    let floating = Rect::new(left, top, self.lateral.size, self.vertical.size);
+    ((left-anchor.left, top-anchor.top), HideFlags::elem(ancestor, floating))
+  }
+}
+
+impl FixedPosSize {
+
+  /// Return adjusted front_fixed_pos(`left` and `top`) with hide flags, considering given position and boundary.
+  /// The boundary is taken as the clipping context of the position.
+  pub fn detect_in(&self, (client_x, client_y): (f64, f64), boundary: &Boundary) -> ((f64, f64), HideFlags) {
+    let (left, top) = self.front_fixed_pos_in((client_x, client_y), boundary);
+    let anchor = Rect::new(client_x, client_y, 0., 0.);
+    let floating = Rect::new(left, top, self.lateral.size, self.vertical.size);
+    ((left, top), HideFlags::new(anchor, floating, boundary.rect()))
+  }
+}
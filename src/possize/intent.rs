@@ -0,0 +1,308 @@
+//! Hover intent: "safe triangle" toward a floating element
+//!
+//! When the pointer moves diagonally from a menu item toward its open submenu, it crosses sibling items,
+//! and the submenu closes before the pointer arrives.
+//!
+//! [`HoverIntent`] tracks pointer movement, and reports whether the pointer is
+//! * on the anchor,
+//! * on the floating element,
+//! * or heading into the floating element: inside of the triangle between the previous pointer position and
+//!   the floating element's near edge. (Check [`safe_triangle()`])
+//!
+//! Rects of the anchor and the floating element are the ones computed by possize. (Ex. `rect` of [`Placed`])
+//!
+//! Heading doesn't last forever: when the pointer rests over a sibling without arriving for `timeout`(300ms by default),
+//! the intent is re-evaluated as moving away.
+//!
+//! * [`HoverIntent::listener()`]: `pointermove` listener closure, in the way of [`pointer_down_move_up`](crate::utils::pointer_down_move_up).
+//!   It re-evaluates the intent when the timeout passes, even without any movement.
+//! * `HoverIntent::init()`: Sycamore signal of the intent, with a handle to reset the tracker. (*feature `sycamore`*)
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let anchor = Rect::new(0., 100., 200., 30.);
+//! let floating = Rect::new(200., 50., 200., 300.);
+//!
+//! // `now`: time in milliseconds
+//! let mut intent = HoverIntent::new();
+//! assert!(intent.track((150., 115.), anchor, floating, 0.)); // on the anchor
+//! assert!(intent.track((170., 135.), anchor, floating, 10.)); // crossing the sibling below, toward the submenu
+//! assert!(!intent.timed_out(200.));
+//! assert!(intent.timed_out(310.)); // resting on the sibling too long
+//! assert!(!intent.track((180., 135.), anchor, floating, 320.));
+//!
+//! intent.reset();
+//! assert!(intent.track((150., 115.), anchor, floating, 400.));
+//! assert!(!intent.track((150., 160.), anchor, floating, 410.)); // moving away
+//! ```
+
+use super::*;
+use std::{cell::Cell, rc::Rc};
+
+/// Triangle between the apex and the floating element's edge facing it.
+/// When the apex is inside of the rect, it's degenerated into the apex itself.
+pub fn safe_triangle((x, y): (f64, f64), floating: Rect) -> [(f64, f64); 3] {
+  let (left, top, right, bottom) = (floating.left, floating.top, floating.right(), floating.bottom());
+  if x<left {
+    [(x, y), (left, top), (left, bottom)]
+  } else if x>right {
+    [(x, y), (right, top), (right, bottom)]
+  } else if y<top {
+    [(x, y), (left, top), (right, top)]
+  } else if y>bottom {
+    [(x, y), (left, bottom), (right, bottom)]
+  } else {
+    [(x, y); 3]
+  }
+}
+
+/// Is the point inside of the triangle? (edges included)
+pub fn is_in_triangle((x, y): (f64, f64), [a, b, c]: [(f64, f64); 3]) -> bool {
+  let cross = |(x0, y0): (f64, f64), (x1, y1): (f64, f64)| (x1-x0)*(y-y0) - (y1-y0)*(x-x0);
+  let (d0, d1, d2) = (cross(a, b), cross(b, c), cross(c, a));
+  let has_neg = d0<0. || d1<0. || d2<0.;
+  let has_pos = d0>0. || d1>0. || d2>0.;
+  !(has_neg && has_pos)
+}
+
+/// Tracker of pointer's hover intent. Check the [module](crate::possize::intent) doc.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoverIntent {
+  /// previous pointer position
+  pub last: Option<(f64, f64)>,
+  /// how long(ms) the pointer may keep heading without arriving. `f64::INFINITY` never times out.
+  pub timeout: f64,
+  /// since when(ms) the pointer has been heading, out of the anchor and the floating element
+  pub heading_since: Option<f64>,
+}
+
+impl Default for HoverIntent {
+  fn default() -> Self {
+    Self { last: None, timeout: 300., heading_since: None }
+  }
+}
+
+impl HoverIntent {
+
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn timeout(mut self, timeout: f64) -> Self {
+    self.timeout = timeout;
+    self
+  }
+
+  /// Track a pointer position at time `now`(ms), and return the intent: is the pointer on the anchor, on the floating element,
+  /// or heading into the floating element within the timeout?
+  ///
+  /// Without any previous position, the pointer is taken as heading.
+  pub fn track(&mut self, point: (f64, f64), anchor: Rect, floating: Rect, now: f64) -> bool {
+    let (x, y) = point;
+    let last = self.last.replace(point);
+
+    if anchor.contains(x, y) || floating.contains(x, y) {
+      self.heading_since = None;
+      return true;
+    }
+    if !last.map(|last| is_in_triangle(point, safe_triangle(last, floating))).unwrap_or(true) {
+      self.heading_since = None;
+      return false;
+    }
+    self.heading_since.get_or_insert(now);
+    !self.timed_out(now)
+  }
+
+  /// Has the pointer been heading longer than the timeout, at time `now`(ms)?
+  pub fn timed_out(&self, now: f64) -> bool {
+    self.heading_since.map(|since| now-since>=self.timeout).unwrap_or(false)
+  }
+
+  /// Forget the previous position. (Ex. when the floating element is closed)
+  pub fn reset(&mut self) {
+    self.last = None;
+    self.heading_since = None;
+  }
+
+  /// `pointermove` listener tracking hover intent with the tracker's timeout. Add it to the document while the floating element is open.
+  ///
+  /// While the pointer is heading, a timer re-evaluates the intent when the timeout passes.
+  /// The timer is cleared when the closure is dropped.
+  ///
+  /// # Args
+  /// * anchor, floating: anchor and floating element
+  /// * get_elem: how to get element from `anchor` and `floating`
+  /// * work: inner closure taking the intent
+  pub fn listener<X: Copy + 'static, E: AsRef<Element>>(
+    self,
+    anchor: X,
+    floating: X,
+    get_elem: impl Fn(X) -> Option<E> + 'static,
+    work: impl Fn(bool) + 'static
+  ) -> Closure<dyn FnMut(PointerEvent)> {
+    Self::shared_listener(Rc::new(Cell::new(self)), anchor, floating, get_elem, work)
+  }
+
+  /// [`listener()`](Self::listener) with a tracker shared outside, so as to be reset.
+  fn shared_listener<X: Copy + 'static, E: AsRef<Element>>(
+    intent: Rc<Cell<Self>>,
+    anchor: X,
+    floating: X,
+    get_elem: impl Fn(X) -> Option<E> + 'static,
+    work: impl Fn(bool) + 'static
+  ) -> Closure<dyn FnMut(PointerEvent)> {
+    let work = Rc::new(work);
+
+    let (intent_, work_) = (intent.clone(), work.clone());
+    let timer = IntentTimer {
+      handle: Cell::new(None),
+      cb: Closure::<dyn FnMut()>::new(move || {
+        if intent_.get().timed_out(now()) {
+          work_(false);
+        }
+      }),
+    };
+
+    Closure::<dyn FnMut(_)>::new(move |e: PointerEvent| {
+      if let (Some(anchor), Some(floating)) = (get_elem(anchor), get_elem(floating)) {
+        let point = (e.client_x() as f64, e.client_y() as f64);
+        let mut tracker = intent.get();
+        let now = now();
+        let heading = tracker.track(point, Rect::elem(anchor), Rect::elem(floating), now);
+        intent.set(tracker);
+
+        timer.clear();
+        if let (true, Some(since)) = (heading, tracker.heading_since) {
+          timer.set(since + tracker.timeout - now);
+        }
+        work(heading);
+      }
+    })
+  }
+
+  /// Signal of hover intent between the anchor and the floating element, with the tracker's timeout.
+  /// `pointermove` listener is added to the document on mount, and removed on cleanup.
+  ///
+  /// The signal starts `true`, as the floating element is usually opened by hovering the anchor.
+  /// On mount, it's seeded with whether the anchor or the floating element is hovered at the moment.
+  /// (Including the given `intent` signal)
+  ///
+  /// Returns [`HoverIntentHandle`] to read the intent and to reset the tracker.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// # use sycamore::prelude::*;
+  /// #[component]
+  /// fn MenuItem<G: Html>() -> View<G> {
+  ///   let anchor_ref = create_node_ref();
+  ///   let floating_ref = create_node_ref();
+  ///   let open = create_signal(false);
+  ///   let intent = HoverIntent::new().timeout(300.).init(anchor_ref, floating_ref, None);
+  ///   let signal = intent.intent;
+  ///
+  ///   create_effect(move || {
+  ///     if !signal.get() {
+  ///       open.set(false);
+  ///     }
+  ///   });
+  ///
+  ///   view! {
+  ///     div(ref=anchor_ref, on:pointerenter=move |_| {
+  ///       // forget the track of the former opening
+  ///       intent.reset();
+  ///       open.set(true);
+  ///     }) {
+  ///       "item"
+  ///       div(ref=floating_ref) { "submenu" }
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn init<G: GenericNode>(
+    self,
+    anchor_ref: NodeRef<G>,
+    floating_ref: NodeRef<G>,
+    intent: Option<Signal<bool>>
+  ) -> HoverIntentHandle {
+
+    let intent = intent.unwrap_or(create_signal(true));
+    let tracker = Rc::new(Cell::new(self));
+    let cb_move = Self::shared_listener(tracker.clone(), anchor_ref, floating_ref, ref_get::<_, Element>, move |x| intent.set(x));
+
+    on_mount(move || {
+      let hovered = |node_ref| ref_get::<_, Element>(node_ref).and_then(|x| x.matches(":hover").ok()).unwrap_or(false);
+      intent.set(hovered(anchor_ref) || hovered(floating_ref));
+
+      let document = gloo_utils::document();
+      document.add_event_listener_with_callback("pointermove", cb_move.as_ref().unchecked_ref()).unwrap_throw();
+      on_cleanup(move || {
+        document.remove_event_listener_with_callback("pointermove", cb_move.as_ref().unchecked_ref()).unwrap_throw();
+      });
+    });
+
+    HoverIntentHandle { intent, tracker }
+  }
+}
+
+/// Handle of [`HoverIntent::init()`]: the intent signal and the tracker behind it.
+///
+/// *feature `sycamore`*
+#[cfg(feature="sycamore")]
+#[derive(Clone)]
+pub struct HoverIntentHandle {
+  /// hover intent
+  pub intent: Signal<bool>,
+  tracker: Rc<Cell<HoverIntent>>,
+}
+
+#[cfg(feature="sycamore")]
+impl HoverIntentHandle {
+
+  /// Get the intent. Check [`HoverIntent::track()`]
+  pub fn get(&self) -> bool {
+    self.intent.get()
+  }
+
+  /// Forget the tracked position and take the intent as `true`. (Ex. when the floating element is opened or closed)
+  /// Check [`HoverIntent::reset()`]
+  pub fn reset(&self) {
+    let mut tracker = self.tracker.get();
+    tracker.reset();
+    self.tracker.set(tracker);
+    self.intent.set(true);
+  }
+}
+
+/// `setTimeout` re-evaluating hover intent. It's cleared when dropped.
+struct IntentTimer {
+  handle: Cell<Option<i32>>,
+  cb: Closure<dyn FnMut()>,
+}
+
+impl IntentTimer {
+
+  fn set(&self, delay: f64) {
+    if delay.is_finite() {
+      let handle = gloo_utils::window()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(self.cb.as_ref().unchecked_ref(), delay.max(0.).ceil() as i32);
+      self.handle.set(handle.ok());
+    }
+  }
+
+  fn clear(&self) {
+    if let Some(handle) = self.handle.take() {
+      gloo_utils::window().clear_timeout_with_handle(handle);
+    }
+  }
+}
+
+impl Drop for IntentTimer {
+  fn drop(&mut self) {
+    self.clear();
+  }
+}
@@ -0,0 +1,141 @@
+//! Measured size of floating elements
+//!
+//! Sizes of possize are supposed to be pre-assigned, and `set_style` forces them as `width` and `height`.
+//! However a menu with dynamic items or localized strings hardly has a size known in advance.
+//!
+//! Instead, let the floating element render with its natural size first
+//! (Ex. { visibility: hidden } while measuring), then
+//! 1. take a copy of possize with the measured size: `measured()`
+//! 2. write only `top` and `left`: `set_pos_style()`
+//!
+//! `set_measured_style()` does both at once.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! // size is unknown yet: only margins are pre-assigned
+//! let possize = FixedPosSize::new((0., 10., 10.), (0., 10., 10.));
+//!
+//! // `with_size()` is what `measured()` does with the rendered size
+//! let possize = possize.with_size((120., 80.));
+//! let boundary = Boundary::Rect(Rect::new(0., 0., 500., 400.));
+//! assert_eq!(possize.front_fixed_pos_in((450., 350.), &boundary), (370., 310.));
+//! ```
+
+use super::*;
+
+/// Get the rendered(border box) size of an element: (width, height)
+pub fn measure_size<E: AsRef<Element>>(elem: E) -> (f64, f64) {
+  let elem = elem.as_ref();
+  (get_box_size(elem, true, MeasureBox::Border), get_box_size(elem, false, MeasureBox::Border))
+}
+
+/// Set style of positions only: `top` and `left`. Sizes are left to the element itself.
+pub fn set_pos_style<H: AsRef<HtmlElement>>(elem: H, (left, top): (f64, f64)) {
+  PosStyle::new().size(false).set_style(elem, Rect::new(left, top, 0., 0.), 0.);
+}
+
+
+impl FixedPosSize {
+
+  /// Return a copy with given size: (width, height)
+  pub fn with_size(&self, (width, height): (f64, f64)) -> Self {
+    let mut possize = *self;
+    possize.lateral.size = width;
+    possize.vertical.size = height;
+    possize
+  }
+
+  /// Return a copy with the element's rendered size
+  pub fn measured<E: AsRef<Element>>(&self, elem: E) -> Self {
+    self.with_size(measure_size(elem))
+  }
+
+  /// Set style of fixed positions only, considering given boundary
+  pub fn set_pos_style_in<H: AsRef<HtmlElement>>(&self, elem: H, client_xy: (f64, f64), boundary: &Boundary) {
+    set_pos_style(elem, self.front_fixed_pos_in(client_xy, boundary));
+  }
+
+  /// Set style of fixed positions only
+  pub fn set_pos_style<H: AsRef<HtmlElement>>(&self, elem: H, client_xy: (f64, f64)) {
+    self.set_pos_style_in(elem, client_xy, &Boundary::Document)
+  }
+
+  /// Measure the rendered element, then set style of fixed positions only. Return the measured possize.
+  pub fn set_measured_style<H: AsRef<HtmlElement>>(&self, elem: H, client_xy: (f64, f64)) -> Self {
+    let possize = self.measured(elem.as_ref());
+    possize.set_pos_style(elem, client_xy);
+    possize
+  }
+}
+
+impl AbsPosSize {
+
+  /// Return a copy with given size: (width, height)
+  pub fn with_size(&self, (width, height): (f64, f64)) -> Self {
+    let mut possize = *self;
+    possize.lateral.size = width;
+    possize.vertical.size = height;
+    possize
+  }
+
+  /// Return a copy with the element's rendered size
+  pub fn measured<E: AsRef<Element>>(&self, elem: E) -> Self {
+    self.with_size(measure_size(elem))
+  }
+
+  /// Set style of absolute positions only, considering given boundary
+  pub fn set_pos_style_in<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H, boundary: &Boundary) {
+    set_pos_style(elem, self.front_absolute_pos_in(ancestor, boundary));
+  }
+
+  /// Set style of absolute positions only
+  pub fn set_pos_style<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H) {
+    self.set_pos_style_in(ancestor, elem, &Boundary::Document)
+  }
+
+  /// Measure the rendered element, then set style of absolute positions only. Return the measured possize.
+  pub fn set_measured_style<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H) -> Self {
+    let possize = self.measured(elem.as_ref());
+    possize.set_pos_style(ancestor, elem);
+    possize
+  }
+}
+
+impl Placed {
+
+  /// Set style of positions only: `top`, `left`
+  pub fn set_pos_style<H: AsRef<HtmlElement>>(&self, elem: H) {
+    set_pos_style(elem, (self.rect.left, self.rect.top));
+  }
+}
+
+impl PlacePosSize {
+
+  /// Return a copy with given size: (width, height)
+  pub fn with_size(&self, (width, height): (f64, f64)) -> Self {
+    let mut possize = self.clone();
+    possize.lateral.size = width;
+    possize.vertical.size = height;
+    possize
+  }
+
+  /// Return a copy with the element's rendered size
+  pub fn measured<E: AsRef<Element>>(&self, elem: E) -> Self {
+    self.with_size(measure_size(elem))
+  }
+
+  /// Measure the rendered element, then set style of fixed positions only. Return chosen placement.
+  pub fn set_measured_fixed_style<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, anchor: E, elem: H) -> Placed {
+    let placed = self.measured(elem.as_ref()).fixed_placed(anchor);
+    placed.set_pos_style(elem);
+    placed
+  }
+
+  /// Measure the rendered element, then set style of absolute positions only. Return chosen placement.
+  pub fn set_measured_absolute_style<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H) -> Placed {
+    let placed = self.measured(elem.as_ref()).absolute_placed(ancestor);
+    placed.set_pos_style(elem);
+    placed
+  }
+}
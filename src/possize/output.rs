@@ -0,0 +1,211 @@
+//! Style output of possize
+//!
+//! `set_style` of possize writes `top`/`left`/`width`/`height` in px.
+//! [`PosStyle`] configures how positions and sizes are written instead:
+//! * `transform`: write `transform: translate3d(..)` with `left: 0; top: 0;`, for GPU-composited movement without layout.
+//! * `rtl`: write `right` instead of `left`, honoring `direction: rtl`.
+//!   Mind that `-start`/`-end` placements of [`PlacePosSize`] are mirrored by its own field `rtl`.
+//! * `size`: write `width`/`height` or not. (Turn it off for measured sizes. Check [`measure`](crate::possize::measure))
+//! * `hide`: write `visibility` by [`HideFlags`] or not, where the flags are detected. (Check [`hide`](crate::possize::hide))
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! assert_eq!(Placement::BottomStart.mirrored(), Placement::BottomEnd);
+//! assert_eq!(Placement::RightStart.mirrored(), Placement::RightStart);
+//!
+//! let mut possize = PlacePosSize::new(Placement::BottomStart, Sizon::abs(4.), (100., 0., 0.), (50., 0., 0.));
+//! possize.rtl = true;
+//! let placed = possize.place(Rect::new(300., 100., 40., 20.), Rect::new(0., 0., 800., 600.));
+//! // aligned with the anchor's right edge
+//! assert_eq!((placed.placement, placed.rect), (Placement::BottomEnd, Rect::new(240., 124., 100., 50.)));
+//!
+//! let pos_style = PosStyle::new().rtl(true);
+//! assert_eq!(pos_style.inline_pos(placed.rect, 800.), 460.);
+//! ```
+
+use super::*;
+
+/// How to write positions and sizes of a floating element
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PosStyle {
+  /// write `transform: translate3d(..)` instead of `left`/`top`
+  pub transform: bool,
+  /// write `right` instead of `left`
+  pub rtl: bool,
+  /// write `width`/`height` or not
+  pub size: bool,
+  /// write `visibility` by hide flags or not
+  #[serde(default)]
+  pub hide: bool,
+}
+
+impl Default for PosStyle {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl PosStyle {
+
+  /// `left`/`top`/`width`/`height`: same with `set_style` of possize
+  pub fn new() -> Self {
+    Self { transform: false, rtl: false, size: true, hide: false }
+  }
+
+  pub fn transform(mut self, transform: bool) -> Self {
+    self.transform = transform;
+    self
+  }
+
+  pub fn rtl(mut self, rtl: bool) -> Self {
+    self.rtl = rtl;
+    self
+  }
+
+  pub fn size(mut self, size: bool) -> Self {
+    self.size = size;
+    self
+  }
+
+  pub fn hide(mut self, hide: bool) -> Self {
+    self.hide = hide;
+    self
+  }
+
+  /// Is the element's computed `direction` rtl?
+  pub fn is_rtl<E: AsRef<Element>>(elem: E) -> bool {
+    gloo_utils::window().get_computed_style(elem.as_ref()).ok().flatten()
+      .and_then(|style| style.get_property_value("direction").ok())
+      .map(|direction| direction=="rtl")
+      .unwrap_or(false)
+  }
+
+  /// Set `rtl` by the element's computed `direction`
+  pub fn direction_of<E: AsRef<Element>>(self, elem: E) -> Self {
+    self.rtl(Self::is_rtl(elem))
+  }
+
+  /// Lateral position to write: `left`, or `right` when `rtl` is on.
+  /// * container_width: width of the containing block, which `right` is relative to.
+  pub fn inline_pos(&self, rect: Rect, container_width: f64) -> f64 {
+    if self.rtl && !self.transform {
+      container_width - rect.right()
+    } else {
+      rect.left
+    }
+  }
+
+  /// Style properties to write for the element's rect: `(property, value)`, where `None` value is to be removed.
+  /// Check [`set_style()`](Self::set_style)
+  ///
+  /// `visibility` is included only when field `hide` is on and hide flags are given.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// let hide = HideFlags { reference_hidden: true, escaped: false };
+  /// let properties = PosStyle::new().size(false).hide(true).properties(Rect::new(10., 20., 0., 0.), 0., Some(hide));
+  /// assert_eq!(properties, vec![
+  ///   ("left", Some("10.00px".to_string())),
+  ///   ("top", Some("20.00px".to_string())),
+  ///   ("visibility", Some("hidden".to_string())),
+  /// ]);
+  /// ```
+  pub fn properties(&self, rect: Rect, container_width: f64, hide: Option<HideFlags>) -> Vec<(&'static str, Option<String>)> {
+    let px = |x: f64| Some(format!("{:.2}px", x));
+    let mut properties = Vec::new();
+
+    if self.size {
+      properties.push(("width", px(rect.width)));
+      properties.push(("height", px(rect.height)));
+    }
+
+    if self.transform {
+      properties.push(("right", None));
+      properties.push(("left", px(0.)));
+      properties.push(("top", px(0.)));
+      properties.push(("transform", Some(format!("translate3d({:.2}px, {:.2}px, 0px)", rect.left, rect.top))));
+    } else {
+      if self.rtl {
+        properties.push(("left", None));
+      }
+      properties.push((if self.rtl { "right" } else { "left" }, px(self.inline_pos(rect, container_width))));
+      properties.push(("top", px(rect.top)));
+    }
+
+    if let Some(hide) = hide.filter(|_| self.hide) {
+      properties.push(("visibility", hide.visibility().map(String::from)));
+    }
+    properties
+  }
+
+  /// Set style of the element's rect. Every `set_style` of possize writes through it.
+  /// * container_width: width of the containing block's padding box(`clientWidth`), which `right` is relative to. It matters only for `rtl`.
+  ///
+  /// Plain `left`/`top` writing doesn't touch `right`, same with possize's own `set_style`.
+  /// `rtl` and `transform` clear the other one. (Clear `right` yourself, when an element turns from rtl to ltr)
+  pub fn set_style<H: AsRef<HtmlElement>>(&self, elem: H, rect: Rect, container_width: f64) {
+    self.set_style_hide(elem, rect, container_width, None);
+  }
+
+  /// [`set_style()`](Self::set_style) with hide flags, which are written when field `hide` is on.
+  pub fn set_style_hide<H: AsRef<HtmlElement>>(&self, elem: H, rect: Rect, container_width: f64, hide: Option<HideFlags>) {
+    let style = elem.as_ref().style();
+    for (property, value) in self.properties(rect, container_width, hide) {
+      let _ = match value {
+        Some(value) => style.set_property(property, &value),
+        None => style.remove_property(property).map(|_| ()),
+      };
+    }
+  }
+}
+
+impl FixedPosSize {
+
+  /// Set style of fixed positions and sizes inside of the boundary, in the way of `pos_style`
+  pub fn set_style_with<H: AsRef<HtmlElement>>(&self, elem: H, client_xy: (f64, f64), boundary: &Boundary, pos_style: &PosStyle) {
+    let ((left, top), hide) = self.detect_in(client_xy, boundary);
+    let rect = Rect::new(left, top, self.lateral.size, self.vertical.size);
+    pos_style.set_style_hide(elem, rect, Rect::document().width, Some(hide));
+  }
+}
+
+impl AbsPosSize {
+
+  /// Set style of absolute positions and sizes inside of the boundary, in the way of `pos_style`.
+  /// The ancestor is supposed to be the containing block.
+  pub fn set_style_with<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H, boundary: &Boundary, pos_style: &PosStyle) {
+    let ancestor = ancestor.as_ref();
+    let ((left, top), hide) = self.detect_in(ancestor, boundary);
+    let rect = Rect::new(left, top, self.lateral.size, self.vertical.size);
+    pos_style.set_style_hide(elem, rect, ancestor.client_width() as f64, Some(hide));
+  }
+}
+
+impl Placed {
+
+  /// Set style of positions and sizes in the way of `pos_style`, with its hide flags.
+  /// * container_width: width of the containing block. It matters only for `rtl`.
+  pub fn set_style_with<H: AsRef<HtmlElement>>(&self, elem: H, pos_style: &PosStyle, container_width: f64) {
+    pos_style.set_style_hide(elem, self.rect, container_width, Some(self.hide));
+  }
+}
+
+impl PlacePosSize {
+
+  /// Set style of fixed positions and sizes inside of the boundary, in the way of `pos_style`. Return chosen placement.
+  pub fn set_fixed_style_with<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, anchor: E, elem: H, boundary: &Boundary, pos_style: &PosStyle) -> Placed {
+    let placed = self.fixed_placed_in(anchor, boundary);
+    placed.set_style_with(elem, pos_style, Rect::document().width);
+    placed
+  }
+
+  /// Set style of absolute positions and sizes inside of the boundary, in the way of `pos_style`. Return chosen placement.
+  pub fn set_absolute_style_with<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H, boundary: &Boundary, pos_style: &PosStyle) -> Placed {
+    let ancestor = ancestor.as_ref();
+    let placed = self.absolute_placed_in(ancestor, boundary);
+    placed.set_style_with(elem, pos_style, ancestor.client_width() as f64);
+    placed
+  }
+}
@@ -0,0 +1,425 @@
+//! Named placements of floating elements
+//!
+//! [`AbsPosSize`] configures each axis separately with `front`/`outward`/`gap`.
+//! Instead, [`PlacePosSize`] takes a named [`Placement`] like `"top"`, `"bottom-start"` or `"right-end"`:
+//! * main axis: the side of the anchor where the floating element goes.
+//! * cross axis: how the floating element is aligned with the anchor. (start, center or end)
+//!
+//! When the floating element goes over the boundary, it tries
+//! * **flip**: the other placements in order of priority. (`fallbacks`, or the opposite side when it's empty)
+//! * **shift**: moving along the cross axis, so as to stay inside of the boundary.
+//!
+//! The chosen placement is returned with the computed position as [`Placed`],
+//! so that its side can be used later, like to style an arrow.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let possize = PlacePosSize::new(Placement::Top, Sizon::abs(8.), (100., 10., 10.), (50., 10., 10.));
+//!
+//! // anchor is at the top of the boundary: not enough space at the top => flipped to the bottom.
+//! let anchor = Rect::new(200., 20., 40., 20.);
+//! let boundary = Rect::new(0., 0., 800., 600.);
+//! let placed = possize.place(anchor, boundary);
+//!
+//! assert_eq!(placed.placement, Placement::Bottom);
+//! assert_eq!(placed.rect, Rect::new(170., 48., 100., 50.));
+//! ```
+
+use super::*;
+
+/// Side of an anchor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+  Top,
+  Right,
+  Bottom,
+  Left,
+}
+
+impl Side {
+
+  pub fn opposite(&self) -> Self {
+    match self {
+      Self::Top => Self::Bottom,
+      Self::Right => Self::Left,
+      Self::Bottom => Self::Top,
+      Self::Left => Self::Right,
+    }
+  }
+
+  /// Is the side on lateral axis? (left or right)
+  pub fn lateral(&self) -> bool {
+    matches!(self, Self::Left | Self::Right)
+  }
+
+  /// Is the side at the front? (left or top)
+  pub fn front(&self) -> bool {
+    matches!(self, Self::Left | Self::Top)
+  }
+
+  /// css property name, like "top"
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Top => "top",
+      Self::Right => "right",
+      Self::Bottom => "bottom",
+      Self::Left => "left",
+    }
+  }
+}
+
+/// Alignment on cross axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Align {
+  Start,
+  Center,
+  End,
+}
+
+/// Named placement of a floating element: side of the anchor and alignment with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Placement {
+  Top,
+  TopStart,
+  TopEnd,
+  Right,
+  RightStart,
+  RightEnd,
+  Bottom,
+  BottomStart,
+  BottomEnd,
+  Left,
+  LeftStart,
+  LeftEnd,
+}
+
+impl Placement {
+
+  /// All 12 placements
+  pub const ALL: [Self; 12] = [
+    Self::Top, Self::TopStart, Self::TopEnd,
+    Self::Right, Self::RightStart, Self::RightEnd,
+    Self::Bottom, Self::BottomStart, Self::BottomEnd,
+    Self::Left, Self::LeftStart, Self::LeftEnd,
+  ];
+
+  pub fn new(side: Side, align: Align) -> Self {
+    match (side, align) {
+      (Side::Top, Align::Center) => Self::Top,
+      (Side::Top, Align::Start) => Self::TopStart,
+      (Side::Top, Align::End) => Self::TopEnd,
+      (Side::Right, Align::Center) => Self::Right,
+      (Side::Right, Align::Start) => Self::RightStart,
+      (Side::Right, Align::End) => Self::RightEnd,
+      (Side::Bottom, Align::Center) => Self::Bottom,
+      (Side::Bottom, Align::Start) => Self::BottomStart,
+      (Side::Bottom, Align::End) => Self::BottomEnd,
+      (Side::Left, Align::Center) => Self::Left,
+      (Side::Left, Align::Start) => Self::LeftStart,
+      (Side::Left, Align::End) => Self::LeftEnd,
+    }
+  }
+
+  pub fn side(&self) -> Side {
+    match self {
+      Self::Top | Self::TopStart | Self::TopEnd => Side::Top,
+      Self::Right | Self::RightStart | Self::RightEnd => Side::Right,
+      Self::Bottom | Self::BottomStart | Self::BottomEnd => Side::Bottom,
+      Self::Left | Self::LeftStart | Self::LeftEnd => Side::Left,
+    }
+  }
+
+  pub fn align(&self) -> Align {
+    match self {
+      Self::Top | Self::Right | Self::Bottom | Self::Left => Align::Center,
+      Self::TopStart | Self::RightStart | Self::BottomStart | Self::LeftStart => Align::Start,
+      Self::TopEnd | Self::RightEnd | Self::BottomEnd | Self::LeftEnd => Align::End,
+    }
+  }
+
+  /// Same alignment at the opposite side
+  pub fn opposite(&self) -> Self {
+    Self::new(self.side().opposite(), self.align())
+  }
+
+  /// Mirrored placement for right-to-left direction: `-start`/`-end` are swapped at the top and bottom sides,
+  /// whose cross axis is the inline(lateral) axis.
+  pub fn mirrored(&self) -> Self {
+    let align = match (self.side().lateral(), self.align()) {
+      (false, Align::Start) => Align::End,
+      (false, Align::End) => Align::Start,
+      (_, align) => align,
+    };
+    Self::new(self.side(), align)
+  }
+
+  /// Literal name, like "top-start"
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Top => "top",
+      Self::TopStart => "top-start",
+      Self::TopEnd => "top-end",
+      Self::Right => "right",
+      Self::RightStart => "right-start",
+      Self::RightEnd => "right-end",
+      Self::Bottom => "bottom",
+      Self::BottomStart => "bottom-start",
+      Self::BottomEnd => "bottom-end",
+      Self::Left => "left",
+      Self::LeftStart => "left-start",
+      Self::LeftEnd => "left-end",
+    }
+  }
+}
+
+impl std::fmt::Display for Placement {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for Placement {
+  type Err = Error;
+
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// assert_eq!("right-end".parse::<Placement>().unwrap(), Placement::RightEnd);
+  /// assert!("middle".parse::<Placement>().is_err());
+  /// ```
+  fn from_str(s: &str) -> Result<Self> {
+    let s = s.trim();
+    Self::ALL.into_iter()
+      .find(|placement| placement.as_str()==s)
+      .ok_or_else(|| Error::Msg(format!("invalid placement: {:?}", s)))
+  }
+}
+
+
+/// Result of placing a floating element
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Placed {
+  /// chosen placement
+  pub placement: Placement,
+  /// floating element's rect
+  pub rect: Rect,
+  /// available size(width, height) at the chosen placement. Check [`PlacePosSize::available_size()`]
+  pub available: (f64, f64),
+  /// flags to hide the floating element. Check [`HideFlags`]
+  pub hide: HideFlags,
+}
+
+impl Placed {
+
+  /// Set style of positions and sizes: `top`, `left`, `width`, `height`
+  pub fn set_style<H: AsRef<HtmlElement>>(&self, elem: H) {
+    PosStyle::new().set_style(elem, self.rect, 0.);
+  }
+}
+
+
+/// # Placement Positioned Size
+///
+/// Place a floating element at a named [`Placement`] of its anchor. Check the [module](crate::possize::placement) doc.
+///
+/// * `gap`: gap between the anchor and the floating element on main axis. Use [`Sizon`].
+///   - `rel` is relative to the anchor's size on main axis. `abs` has higher priority. Fallback is 0.
+/// * `lateral`/`vertical`: the floating element's size and its margins to the boundary. Check [`FixedUniPosSize`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlacePosSize {
+  /// preferred placement
+  pub placement: Placement,
+  /// placements to try in order when `placement` goes over the boundary.
+  /// If it's empty, the opposite placement is tried.
+  pub fallbacks: Vec<Placement>,
+  /// try fallback placements or not
+  pub flip: bool,
+  /// move along the cross axis to stay inside of the boundary or not
+  pub shift: bool,
+  /// shrink into the available size or not. Check [`fit`](crate::possize::fit)
+  #[serde(default)]
+  pub fit: bool,
+  /// right-to-left direction: `-start`/`-end` placements are mirrored. Check [`Placement::mirrored()`]
+  #[serde(default)]
+  pub rtl: bool,
+  /// gap between the anchor and oneself
+  pub gap: Sizon,
+  /// lateral size and margins
+  pub lateral: FixedUniPosSize,
+  /// vertical size and margins
+  pub vertical: FixedUniPosSize,
+}
+
+impl PlacePosSize {
+
+  /// New placement possize with flip and shift on.
+  ///
+  /// # Args
+  /// (size_x, front_margin_x, rear_margin_x): (f64, f64, f64),
+  ///
+  /// (size_y, front_margin_y, rear_margin_y): (f64, f64, f64)
+  pub fn new(
+    placement: Placement,
+    gap: Sizon,
+    (size_x, front_margin_x, rear_margin_x): (f64, f64, f64),
+    (size_y, front_margin_y, rear_margin_y): (f64, f64, f64),
+  ) -> Self {
+    Self {
+      placement,
+      fallbacks: vec![],
+      flip: true,
+      shift: true,
+      fit: false,
+      rtl: false,
+      gap,
+      lateral: FixedUniPosSize::new(size_x, front_margin_x, rear_margin_x),
+      vertical: FixedUniPosSize::new(size_y, front_margin_y, rear_margin_y),
+    }
+  }
+
+  pub(super) fn uni(&self, lateral: bool) -> &FixedUniPosSize {
+    if lateral { &self.lateral } else { &self.vertical }
+  }
+
+  /// Placements to try in order of priority. They're mirrored when `rtl` is on.
+  pub fn candidates(&self) -> Vec<Placement> {
+    let mut candidates = vec![self.placement];
+    if self.flip {
+      let fallbacks = if self.fallbacks.is_empty() { vec![self.placement.opposite()] } else { self.fallbacks.clone() };
+      for placement in fallbacks {
+        if !candidates.contains(&placement) {
+          candidates.push(placement);
+        }
+      }
+    }
+    if self.rtl {
+      candidates.iter_mut().for_each(|placement| *placement = placement.mirrored());
+    }
+    candidates
+  }
+
+  /// Floating element's rect at given placement, without any adjustment
+  pub fn raw_rect(&self, placement: Placement, anchor: Rect) -> Rect {
+    let side = placement.side();
+    let main_lateral = side.lateral();
+    let gap = self.gap.to_abs(anchor.size(main_lateral)).unwrap_or(0.);
+
+    let main_size = self.uni(main_lateral).size;
+    let main_pos = if side.front() {
+      anchor.front(main_lateral) - gap - main_size
+    } else {
+      anchor.rear(main_lateral) + gap
+    };
+
+    let cross_size = self.uni(!main_lateral).size;
+    let cross_pos = match placement.align() {
+      Align::Start => anchor.front(!main_lateral),
+      Align::Center => anchor.front(!main_lateral) + (anchor.size(!main_lateral) - cross_size)/2.,
+      Align::End => anchor.rear(!main_lateral) - cross_size,
+    };
+
+    if main_lateral {
+      Rect::new(main_pos, cross_pos, main_size, cross_size)
+    } else {
+      Rect::new(cross_pos, main_pos, cross_size, main_size)
+    }
+  }
+
+  /// How much does the rect go over the boundary on given axis?
+  fn overflow(&self, rect: Rect, boundary: Rect, lateral: bool) -> f64 {
+    self.uni(lateral).overflow(rect.front(lateral) - boundary.front(lateral), boundary.size(lateral))
+  }
+
+  /// Place the floating element around the `anchor` inside of the `boundary`. Both are in the same coordinates.
+  ///
+  /// 1. Try candidate placements in order, and choose the first one not going over the boundary on main axis.
+  ///    If every candidate goes over, the one with the least overflow is chosen.
+  /// 2. If `shift` is on, move it along the cross axis to stay inside of the boundary.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// let mut possize = PlacePosSize::new(Placement::Top, Sizon::abs(8.), (100., 10., 10.), (50., 10., 10.));
+  /// let boundary = Rect::new(0., 0., 800., 600.);
+  ///
+  /// // shifted from the left edge: -30 => 10
+  /// let placed = possize.place(Rect::new(0., 300., 40., 20.), boundary);
+  /// assert_eq!((placed.placement, placed.rect.left, placed.rect.top), (Placement::Top, 10., 242.));
+  ///
+  /// // not flipped nor shifted
+  /// possize.flip = false;
+  /// possize.shift = false;
+  /// let placed = possize.place(Rect::new(0., 20., 40., 20.), boundary);
+  /// assert_eq!((placed.placement, placed.rect.left, placed.rect.top), (Placement::Top, -30., -38.));
+  ///
+  /// // fallbacks in order
+  /// possize.flip = true;
+  /// possize.fallbacks = vec![Placement::RightStart, Placement::Bottom];
+  /// let placed = possize.place(Rect::new(0., 20., 40., 20.), boundary);
+  /// assert_eq!((placed.placement, placed.rect.left, placed.rect.top), (Placement::RightStart, 48., 20.));
+  /// ```
+  pub fn place(&self, anchor: Rect, boundary: Rect) -> Placed {
+
+    let mut best: Option<(Placement, Rect, f64)> = None;
+    for placement in self.candidates() {
+      let rect = self.raw_rect(placement, anchor);
+      let over = self.overflow(rect, boundary, placement.side().lateral());
+      if best.map(|(.., over_)| over<over_).unwrap_or(true) {
+        best = Some((placement, rect, over));
+      }
+      if over<=0. {
+        break;
+      }
+    }
+    let (placement, mut rect, _) = best.unwrap_or((self.placement, self.raw_rect(self.placement, anchor), 0.));
+
+    let available = self.available_size(placement, anchor, boundary);
+    if self.fit {
+      rect = self.fit_rect(placement, rect, available);
+    }
+
+    if self.shift {
+      let cross_lateral = !placement.side().lateral();
+      let uni = FixedUniPosSize { size: rect.size(cross_lateral), ..*self.uni(cross_lateral) };
+      let mut pos = rect.front(cross_lateral) - boundary.front(cross_lateral);
+      if uni.is_over(pos, boundary.size(cross_lateral)) {
+        uni.adjust_front_pos(&mut pos, boundary.size(cross_lateral));
+        pos += boundary.front(cross_lateral);
+        if cross_lateral { rect.left = pos; } else { rect.top = pos; }
+      }
+    }
+
+    let hide = HideFlags::new(anchor, rect, boundary);
+    Placed { placement, rect, available, hide }
+  }
+
+  /// Place around the anchor element for { position: fixed }.
+  /// Browser's client area is the boundary.
+  pub fn fixed_placed<E: AsRef<Element>>(&self, anchor: E) -> Placed {
+    self.fixed_placed_in(anchor, &Boundary::Document)
+  }
+
+  /// Place around the ancestor element for { position: absolute }.
+  /// Returned rect is relative to the ancestor, which is supposed to be the floating element's containing block.
+  pub fn absolute_placed<E: AsRef<Element>>(&self, ancestor: E) -> Placed {
+    self.absolute_placed_in(ancestor, &Boundary::Document)
+  }
+
+  /// Set style of fixed positions and sizes. Return chosen placement.
+  pub fn set_fixed_style<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, anchor: E, elem: H) -> Placed {
+    let placed = self.fixed_placed(anchor);
+    placed.set_style(elem);
+    placed
+  }
+
+  /// Set style of absolute positions and sizes. Return chosen placement.
+  pub fn set_absolute_style<E: AsRef<Element>, H: AsRef<HtmlElement>>(&self, ancestor: E, elem: H) -> Placed {
+    let placed = self.absolute_placed(ancestor);
+    placed.set_style(elem);
+    placed
+  }
+}
@@ -0,0 +1,168 @@
+//! Cascading submenus
+//!
+//! A submenu opens to the side of its parent menu item, and aligns its first item with the hovered item.
+//! When it runs out of space, it flips to the other side of the **whole parent menu**, not of the item:
+//! composing [`AbsPosSize`] with the item only would overlap the parent menu when flipping.
+//!
+//! [`SubmenuPosSize`] knows both of the item's rect and the parent menu's rect.
+//! The result is [`Placed`] with `right-start` or `left-start` placement,
+//! and [`SubmenuPosSize::cascaded()`] lets the next level keep going in the same direction.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let submenu = SubmenuPosSize::new((200., 10., 10.), (300., 10., 10.), 2., 4.);
+//! let boundary = Rect::new(0., 0., 800., 600.);
+//!
+//! // parent menu at the right side of the screen: flipped to the left of the whole menu.
+//! let menu = Rect::new(450., 100., 200., 400.);
+//! let item = Rect::new(450., 140., 200., 30.);
+//! let placed = submenu.place(item, menu, boundary);
+//! assert_eq!(placed.placement, Placement::LeftStart);
+//! assert_eq!(placed.rect, Rect::new(248., 136., 200., 300.));
+//!
+//! // the next level keeps going to the left
+//! assert_eq!(submenu.cascaded(&placed).side, Side::Left);
+//!
+//! // item near the bottom: shifted up to stay inside
+//! let item = Rect::new(450., 460., 200., 30.);
+//! let placed = submenu.place(item, menu, boundary);
+//! assert_eq!(placed.rect.top, 290.);
+//! ```
+
+use super::*;
+
+/// # Submenu Positioned Size
+///
+/// * `lateral`/`vertical`: the submenu's size and its margins to the boundary. Check [`FixedUniPosSize`].
+/// * `gap`: lateral gap between the parent menu and the submenu.
+/// * `offset`: how far the submenu goes up from the item's top, to align its first item with the item.
+///   (Ex. the submenu's padding-top)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SubmenuPosSize {
+  /// preferred side of the parent menu: `Right` or `Left`
+  pub side: Side,
+  /// gap between the parent menu and oneself
+  pub gap: f64,
+  /// upward offset from the item's top
+  pub offset: f64,
+  /// lateral size and margins
+  pub lateral: FixedUniPosSize,
+  /// vertical size and margins
+  pub vertical: FixedUniPosSize,
+}
+
+impl SubmenuPosSize {
+
+  /// New submenu possize, preferring the right side.
+  ///
+  /// # Args
+  /// (size_x, front_margin_x, rear_margin_x): (f64, f64, f64),
+  ///
+  /// (size_y, front_margin_y, rear_margin_y): (f64, f64, f64),
+  ///
+  /// gap: f64, offset: f64
+  pub fn new(
+    (size_x, front_margin_x, rear_margin_x): (f64, f64, f64),
+    (size_y, front_margin_y, rear_margin_y): (f64, f64, f64),
+    gap: f64,
+    offset: f64
+  ) -> Self {
+    Self {
+      side: Side::Right,
+      gap,
+      offset,
+      lateral: FixedUniPosSize::new(size_x, front_margin_x, rear_margin_x),
+      vertical: FixedUniPosSize::new(size_y, front_margin_y, rear_margin_y),
+    }
+  }
+
+  /// Copy for the next level, preferring the side where this level is placed.
+  pub fn cascaded(&self, placed: &Placed) -> Self {
+    Self { side: placed.placement.side(), ..*self }
+  }
+
+  /// Submenu's left at given side of the parent menu
+  fn left_at(&self, side: Side, menu: Rect) -> f64 {
+    if side==Side::Left {
+      menu.left - self.gap - self.lateral.size
+    } else {
+      menu.right() + self.gap
+    }
+  }
+
+  /// Place the submenu beside the parent menu, aligned with the item, inside of the boundary.
+  /// All rects are in the same coordinates.
+  ///
+  /// 1. Lateral: the preferred side of the parent menu. When it goes over the boundary, the other side is tried.
+  ///    If both go over, the one with less overflow is chosen and adjusted.
+  /// 2. Vertical: the item's top minus `offset`, adjusted to stay inside of the boundary.
+  pub fn place(&self, item: Rect, menu: Rect, boundary: Rect) -> Placed {
+
+    let side = if self.side==Side::Left { Side::Left } else { Side::Right };
+    let overflow = |side: Side| self.lateral.overflow(self.left_at(side, menu) - boundary.left, boundary.width);
+
+    let over = overflow(side);
+    let side = if over>0. && overflow(side.opposite())<over { side.opposite() } else { side };
+
+    let mut left = self.left_at(side, menu) - boundary.left;
+    self.lateral.adjust_front_pos(&mut left, boundary.width);
+    let top = self.vertical.front_fixed_pos(item.top - self.offset - boundary.top, boundary.height);
+
+    let available_width = if side==Side::Left {
+      menu.left - self.gap - boundary.left - self.lateral.front_margin
+    } else {
+      boundary.right() - self.lateral.rear_margin - menu.right() - self.gap
+    }.max(0.);
+
+    let rect = Rect::new(left + boundary.left, top + boundary.top, self.lateral.size, self.vertical.size);
+    Placed {
+      placement: Placement::new(side, Align::Start),
+      rect,
+      available: (available_width, self.vertical.max_size(boundary.height)),
+      hide: HideFlags::new(item, rect, boundary),
+    }
+  }
+
+  /// Place for { position: fixed }, inside of the boundary.
+  pub fn fixed_placed_in<E: AsRef<Element>, M: AsRef<Element>>(&self, item: E, menu: M, boundary: &Boundary) -> Placed {
+    self.place(Rect::elem(item), Rect::elem(menu), boundary.rect())
+  }
+
+  /// Place for { position: absolute }, inside of the boundary.
+  /// Returned rect is relative to the item, which is supposed to be the submenu's containing block.
+  pub fn absolute_placed_in<E: AsRef<Element>, M: AsRef<Element>>(&self, item: E, menu: M, boundary: &Boundary) -> Placed {
+    let item = Rect::elem(item);
+    let mut placed = self.place(item, Rect::elem(menu), boundary.rect());
+    placed.rect = placed.rect.translate(-item.left, -item.top);
+    placed
+  }
+
+  /// Set style of fixed positions and sizes inside of the boundary. Return chosen placement.
+  pub fn set_fixed_style_in<E: AsRef<Element>, M: AsRef<Element>, H: AsRef<HtmlElement>>(
+    &self, item: E, menu: M, elem: H, boundary: &Boundary
+  ) -> Placed {
+    let placed = self.fixed_placed_in(item, menu, boundary);
+    placed.set_style(elem);
+    placed
+  }
+
+  /// Set style of absolute positions and sizes inside of the boundary. Return chosen placement.
+  pub fn set_absolute_style_in<E: AsRef<Element>, M: AsRef<Element>, H: AsRef<HtmlElement>>(
+    &self, item: E, menu: M, elem: H, boundary: &Boundary
+  ) -> Placed {
+    let placed = self.absolute_placed_in(item, menu, boundary);
+    placed.set_style(elem);
+    placed
+  }
+
+  /// Set style of fixed positions and sizes. Browser's client area is the boundary.
+  pub fn set_fixed_style<E: AsRef<Element>, M: AsRef<Element>, H: AsRef<HtmlElement>>(&self, item: E, menu: M, elem: H) -> Placed {
+    self.set_fixed_style_in(item, menu, elem, &Boundary::Document)
+  }
+
+  /// Set style of absolute positions and sizes. Browser's client area is the boundary.
+  pub fn set_absolute_style<E: AsRef<Element>, M: AsRef<Element>, H: AsRef<HtmlElement>>(&self, item: E, menu: M, elem: H) -> Placed {
+    self.set_absolute_style_in(item, menu, elem, &Boundary::Document)
+  }
+}
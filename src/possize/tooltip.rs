@@ -0,0 +1,326 @@
+//! Tooltip controller
+//!
+//! [`AbsPosSize`] positions a tooltip, while its interaction needs a state machine:
+//! * show/hide delays
+//! * triggers of pointer hover(`pointerenter`/`pointerleave`) and keyboard focus(`focusin`/`focusout`)
+//! * shared "warm-up": right after a tooltip is hidden, adjacent tooltips show up instantly
+//! * `Escape` to dismiss
+//! * `aria-describedby` wiring between the anchor and the tooltip
+//!
+//! [`Tooltip::new()`] is the generic closure-based core, like [`scrolling_listeners()`](crate::ScrollMetric::scrolling_listeners).
+//! `Tooltip::init_tooltip()` wraps it for Sycamore. (*feature `sycamore`*)
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! let delay = TooltipDelay::default();
+//! assert_eq!(delay.delay(true, false), 500);
+//! assert_eq!(delay.delay(true, true), 0); // warmed up
+//! assert_eq!(delay.delay(false, true), 100);
+//! ```
+
+use super::*;
+use std::{cell::Cell, rc::Rc};
+use web_sys::{EventTarget, KeyboardEvent};
+
+thread_local! {
+  /// shared warm-up: tooltips show up instantly until this time(`performance.now()`)
+  static WARM_UNTIL: Cell<f64> = const { Cell::new(0.) };
+}
+
+/// Delays of a tooltip in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TooltipDelay {
+  /// delay to show up
+  pub show: i32,
+  /// delay to hide
+  pub hide: i32,
+  /// duration of warm-up after any tooltip is hidden
+  pub warmup: i32,
+}
+
+impl Default for TooltipDelay {
+  fn default() -> Self {
+    Self { show: 500, hide: 100, warmup: 300 }
+  }
+}
+
+impl TooltipDelay {
+
+  pub fn new(show: i32, hide: i32, warmup: i32) -> Self {
+    Self { show, hide, warmup }
+  }
+
+  /// Delay to show(true) or hide(false), considering warm-up
+  pub fn delay(&self, show: bool, warm: bool) -> i32 {
+    if !show {
+      self.hide
+    } else if warm {
+      0
+    } else {
+      self.show
+    }
+  }
+
+  /// Is any tooltip shown, or hidden just before?
+  pub fn is_warm() -> bool {
+    WARM_UNTIL.with(|x| now()<x.get())
+  }
+}
+
+/// State machine of a tooltip
+struct TooltipCore {
+  delay: TooltipDelay,
+  hovered: Cell<bool>,
+  focused: Cell<bool>,
+  shown: Cell<bool>,
+  /// state to be applied when the timer fires
+  pending: Cell<bool>,
+  timer: Cell<Option<i32>>,
+  work: Box<dyn Fn(bool)>,
+}
+
+impl TooltipCore {
+
+  fn cancel(&self) {
+    if let Some(timer) = self.timer.take() {
+      gloo_utils::window().clear_timeout_with_handle(timer);
+    }
+  }
+
+  fn set(&self, show: bool) {
+    self.cancel();
+    if self.shown.get()==show {
+      return;
+    }
+    self.shown.set(show);
+    let warm_until = if show { f64::INFINITY } else { now() + self.delay.warmup as f64 };
+    WARM_UNTIL.with(|x| x.set(warm_until));
+    (self.work)(show);
+  }
+
+  /// Show or hide by the triggers, after delay
+  fn request(&self, cb_timeout: &Closure<dyn FnMut()>) {
+    self.cancel();
+    let show = self.hovered.get() || self.focused.get();
+    if show==self.shown.get() {
+      return;
+    }
+    let delay = self.delay.delay(show, TooltipDelay::is_warm());
+    if delay<=0 {
+      self.set(show);
+    } else {
+      self.pending.set(show);
+      let timer = gloo_utils::window()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(cb_timeout.as_ref().unchecked_ref(), delay);
+      self.timer.set(timer.ok());
+    }
+  }
+}
+
+/// Tooltip controller: listeners of the anchor and the document.
+///
+/// Timers are cleared when it's dropped. Remove its listeners before dropping it.
+pub struct Tooltip {
+  core: Rc<TooltipCore>,
+  cb_pointerenter: Closure<dyn FnMut(PointerEvent)>,
+  cb_pointerleave: Closure<dyn FnMut(PointerEvent)>,
+  cb_focusin: Closure<dyn FnMut(Event)>,
+  cb_focusout: Closure<dyn FnMut(Event)>,
+  cb_keydown: Closure<dyn FnMut(KeyboardEvent)>,
+}
+
+impl Tooltip {
+
+  /// # Args
+  /// * delay: delays of showing and hiding
+  /// * work: inner closure to show(true) or hide(false) the tooltip
+  pub fn new(delay: TooltipDelay, work: impl Fn(bool) + 'static) -> Self {
+
+    let core = Rc::new(TooltipCore {
+      delay,
+      hovered: Cell::new(false),
+      focused: Cell::new(false),
+      shown: Cell::new(false),
+      pending: Cell::new(false),
+      timer: Cell::new(None),
+      work: Box::new(work),
+    });
+
+    let core_ = core.clone();
+    let cb_timeout = Rc::new(Closure::<dyn FnMut()>::new(move || {
+      core_.timer.set(None);
+      core_.set(core_.pending.get());
+    }));
+
+    let trigger = |set: fn(&TooltipCore)| {
+      let (core, cb_timeout) = (core.clone(), cb_timeout.clone());
+      move || {
+        set(&core);
+        core.request(&cb_timeout);
+      }
+    };
+
+    let enter = trigger(|core| core.hovered.set(true));
+    let leave = trigger(|core| core.hovered.set(false));
+    let focus = trigger(|core| core.focused.set(true));
+    let blur = trigger(|core| core.focused.set(false));
+
+    let core_ = core.clone();
+    let cb_keydown = Closure::<dyn FnMut(_)>::new(move |e: KeyboardEvent| {
+      if e.key()=="Escape" && core_.shown.get() {
+        // dismissed until the next trigger
+        core_.hovered.set(false);
+        core_.focused.set(false);
+        core_.set(false);
+      }
+    });
+
+    Self {
+      core,
+      cb_pointerenter: Closure::<dyn FnMut(_)>::new(move |_: PointerEvent| enter()),
+      cb_pointerleave: Closure::<dyn FnMut(_)>::new(move |_: PointerEvent| leave()),
+      cb_focusin: Closure::<dyn FnMut(_)>::new(move |_: Event| focus()),
+      cb_focusout: Closure::<dyn FnMut(_)>::new(move |_: Event| blur()),
+      cb_keydown,
+    }
+  }
+
+  /// Is the tooltip shown?
+  pub fn shown(&self) -> bool {
+    self.core.shown.get()
+  }
+
+  /// Show or hide immediately, regardless of triggers and delays
+  pub fn set(&self, show: bool) {
+    self.core.set(show);
+  }
+
+  fn listeners(&self) -> [(&str, &JsValue); 4] {
+    [
+      ("pointerenter", self.cb_pointerenter.as_ref()),
+      ("pointerleave", self.cb_pointerleave.as_ref()),
+      ("focusin", self.cb_focusin.as_ref()),
+      ("focusout", self.cb_focusout.as_ref()),
+    ]
+  }
+
+  /// Add listeners to the anchor, and `keydown` listener to the document
+  pub fn add<T: AsRef<EventTarget>>(&self, anchor: T) {
+    for (event, cb) in self.listeners() {
+      let _ = anchor.as_ref().add_event_listener_with_callback(event, cb.unchecked_ref());
+    }
+    let _ = gloo_utils::document().add_event_listener_with_callback("keydown", self.cb_keydown.as_ref().unchecked_ref());
+  }
+
+  /// Remove listeners from the anchor and the document
+  pub fn remove<T: AsRef<EventTarget>>(&self, anchor: T) {
+    for (event, cb) in self.listeners() {
+      let _ = anchor.as_ref().remove_event_listener_with_callback(event, cb.unchecked_ref());
+    }
+    let _ = gloo_utils::document().remove_event_listener_with_callback("keydown", self.cb_keydown.as_ref().unchecked_ref());
+  }
+
+  /// Wire `aria-describedby` of the anchor to the tooltip's id, or unwire it.
+  /// The tooltip gets `role="tooltip"`, and a generated id if it has none.
+  pub fn set_described_by<E: AsRef<Element>, T: AsRef<Element>>(anchor: E, tooltip: T, on: bool) {
+    let (anchor, tooltip) = (anchor.as_ref(), tooltip.as_ref());
+    if tooltip.id().is_empty() {
+      tooltip.set_id(&format!("webtric-tooltip-{}", next_tooltip_id()));
+    }
+    let _ = tooltip.set_attribute("role", "tooltip");
+
+    let id = tooltip.id();
+    let ids = anchor.get_attribute("aria-describedby").unwrap_or_default();
+    let mut ids: Vec<&str> = ids.split_whitespace().filter(|x| *x!=id).collect();
+    if on {
+      ids.push(&id);
+    }
+    if ids.is_empty() {
+      let _ = anchor.remove_attribute("aria-describedby");
+    } else {
+      let _ = anchor.set_attribute("aria-describedby", &ids.join(" "));
+    }
+  }
+
+  /// Tooltip controller for Sycamore.
+  /// Listeners are added on mount and removed on cleanup.
+  /// When it shows up, the tooltip is positioned by `possize` and wired by `aria-describedby`.
+  ///
+  /// # Outputs
+  /// (anchor_ref, tooltip_ref, signal of being shown)
+  ///
+  /// The tooltip's visibility is up to the signal.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// # use sycamore::prelude::*;
+  /// #[component]
+  /// fn Component<G: Html>() -> View<G> {
+  ///   let possize = AbsPosSize::new(
+  ///     (true, false, Sizon::abs(0.), 120., 8., 8.),
+  ///     (true, true, Sizon::abs(4.), 30., 8., 8.)
+  ///   );
+  ///   let (anchor_ref, tooltip_ref, shown) = Tooltip::init_tooltip(None, None, possize, TooltipDelay::default());
+  ///
+  ///   view! {
+  ///     button(ref=anchor_ref, style="position: relative;") {
+  ///       "save"
+  ///       div(ref=tooltip_ref, style="position: absolute;") { "save the document" }
+  ///     }
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// *feature `sycamore`*
+  #[cfg(feature="sycamore")]
+  pub fn init_tooltip<G: GenericNode>(
+    anchor_ref: Option<NodeRef<G>>,
+    tooltip_ref: Option<NodeRef<G>>,
+    possize: AbsPosSize,
+    delay: TooltipDelay
+  ) -> (NodeRef<G>, NodeRef<G>, Signal<bool>) {
+
+    let anchor_ref: NodeRef<G> = anchor_ref.unwrap_or(create_node_ref());
+    let tooltip_ref: NodeRef<G> = tooltip_ref.unwrap_or(create_node_ref());
+    let shown = create_signal(false);
+
+    on_mount(move || {
+      create_effect(on(shown, move || {
+        if let (Some(anchor), Some(tooltip)) = (ref_get::<_, Element>(anchor_ref), ref_get::<_, HtmlElement>(tooltip_ref)) {
+          if shown.get() {
+            possize.set_style(&anchor, &tooltip);
+          }
+          Self::set_described_by(&anchor, &tooltip, shown.get());
+        }
+      }));
+
+      let tooltip = Self::new(delay, move |x| shown.set(x));
+      if let Some(anchor) = ref_get::<_, EventTarget>(anchor_ref) {
+        tooltip.add(&anchor);
+        on_cleanup(move || tooltip.remove(&anchor));
+      }
+    });
+
+    (anchor_ref, tooltip_ref, shown)
+  }
+}
+
+impl Drop for Tooltip {
+  fn drop(&mut self) {
+    self.core.cancel();
+  }
+}
+
+thread_local! {
+  static TOOLTIP_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+fn next_tooltip_id() -> usize {
+  TOOLTIP_ID.with(|x| {
+    let id = x.get();
+    x.set(id+1);
+    id
+  })
+}
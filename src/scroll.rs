@@ -107,17 +107,26 @@ impl UniScrollMetric {
   /// return metric from given scrolling element.
   /// Use [ScrollMetric] to capture both of x y direction.
   pub fn measures<E: AsRef<Element>>(scrolling: E, lateral: bool) -> Self {
+    Self::measures_box(scrolling, lateral, MeasureBox::Client)
+  }
+
+  /// return metric from given scrolling element, measuring its client size with given box. (See [`MeasureBox`])
+  /// 
+  /// Default of [`measures()`](Self::measures) is `Client`.
+  /// Ex. `Content` excludes the scrolling element's padding, which is not scrolled through by the thumb.
+  pub fn measures_box<E: AsRef<Element>>(scrolling: E, lateral: bool, measure: MeasureBox) -> Self {
 
     let elem = scrolling.as_ref();
     
-    // <!> For scrolling element's client size, use `clientWidth/Height`, not `getBoundingClientRect`.
+    // <!> For scrolling element's client size, prefer `clientWidth/Height`(`MeasureBox::Client`), not `getBoundingClientRect`.
     // Because getBoundingClientRect would calculate hidden area as long as it's inside the viewPort.
     // However, clientWidth/Height would only consider visible area (for the case of scrolling elements, which have designated style size).
-    let (client_size, scroll_size, scroll_pos) = if lateral {
-      (elem.client_width() as f64, elem.scroll_width() as f64, elem.scroll_left() as f64)
+    let (scroll_size, scroll_pos) = if lateral {
+      (elem.scroll_width() as f64, elem.scroll_left() as f64)
     } else {
-      (elem.client_height() as f64, elem.scroll_height() as f64, elem.scroll_top() as f64)
+      (elem.scroll_height() as f64, elem.scroll_top() as f64)
     };
+    let client_size = get_box_size(elem, lateral, measure);
   
     Self { client_size, scroll_size, scroll_pos }
   }
@@ -147,6 +156,14 @@ impl ScrollMetric {
     }
   }
 
+  /// return metric, measuring client sizes with given box. Check [`UniScrollMetric::measures_box()`]
+  pub fn measures_box<E: AsRef<Element>>(scrolling: E, measure: MeasureBox) -> Self {
+    Self {
+      x: UniScrollMetric::measures_box(scrolling.as_ref(), true, measure),
+      y: UniScrollMetric::measures_box(scrolling.as_ref(), false, measure)
+    }
+  }
+
   /// manual scroll by given `delta` with given direction(lateral or vertical)
   pub fn scroll_by<E: AsRef<Element>>(scrolling: E, delta: f64, lateral: bool) {
     let (x, y) = if lateral { (delta, 0.) } else { (0., delta) };
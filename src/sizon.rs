@@ -10,9 +10,31 @@
 //! It consolidates the relativeness and the absoluteness.
 //! 
 //! It's named to be feel like particulate, something like boson, grviton, uhuh ... huh.. size entanglement?!
+//! 
+//! ## Arithmetic
+//! Sizon's `abs` and `rel` are alternatives, so Sizon itself has no arithmetic.
+//! For sizes like "half the parent minus the gutter", which sum up both of them, check mod [`calc`].
+//! 
+//! ## Parsing
+//! Sizon can be parsed from css length literals: `"20px"`, `"35%"`, a pure ratio `"0.35"`,
+//! and simple `calc()` forms like `"calc(50% - 12px)"`.
+//! Thus it also works with [`parse_dataset()`] for html dataset values.
+//! ```
+//! # use webtric::*;
+//! assert_eq!("20px".parse::<Sizon>().unwrap(), Sizon::abs(20.));
+//! assert_eq!(Sizon::try_from("calc(50% - 12px)").unwrap(), Sizon::new(Some(-12.), Some(0.5)));
+//! ```
+//! 
+//! ## Units
+//! For font or viewport relative units(`em`, `rem`, `vw`, `vh`, `vmin`, `vmax`), check mod [`unit`].
 
 use crate::*;
 
+pub mod calc;
+pub use calc::*;
+pub mod unit;
+pub use unit::*;
+
 /// Sizon has just two fields: `abs` and `rel`.
 /// 
 /// `abs` would refer to in-pixel size while `rel` would refer to relative-to-parent(ancestor) size ratio.
@@ -161,12 +183,64 @@ impl Sizon {
     abs
   }
 
+  /// Regarding the Sizon as a minimum limitation, return the lower limit size with given parent's size(`par`).
+  /// When both of `abs` and `rel` are some, the larger(stricter) one is used. Fallback is zero.
+  /// 
+  /// # Example
+  /// ```
+  /// # use webtric::Sizon;
+  /// assert_eq!(Sizon::new(Some(20.), Some(0.1)).min_limit(300.), 30.);
+  /// assert_eq!(Sizon::default().min_limit(300.), 0.);
+  /// ```
+  pub fn min_limit(&self, par: f64) -> f64 {
+    if let Some(abs) = self.abs {
+      if let Some(rel) = self.rel {
+        abs.max(rel*par) // use .max(), as it's for limitation check
+      } else {
+        abs
+      }
+    } else if let Some(rel) = self.rel {
+      rel*par
+    } else {
+      0.
+    }
+  }
+
+  /// Regarding the Sizon as a maximum limitation, return the upper limit size with given parent's size(`par`).
+  /// When both of `abs` and `rel` are some, the smaller(stricter) one is used. Return None when there is no limitation.
+  /// 
+  /// # Example
+  /// ```
+  /// # use webtric::Sizon;
+  /// assert_eq!(Sizon::new(Some(20.), Some(0.1)).max_limit(300.), Some(20.));
+  /// assert_eq!(Sizon::default().max_limit(300.), None);
+  /// ```
+  pub fn max_limit(&self, par: f64) -> Option<f64> {
+    if let Some(abs) = self.abs {
+      if let Some(rel) = self.rel {
+        Some(abs.min(rel*par)) // use .min(), cuz it's for limitation check
+      } else {
+        Some(abs)
+      }
+    } else {
+      self.rel.map(|rel| rel*par)
+    }
+  }
+
   /// Build a new Sizon from a given element.
   /// Use element's and its parent's DomRect size.
   pub fn elem<E: AsRef<Element>>(elem: E, lateral: bool) -> Self {
+    Self::elem_box(elem, lateral, MeasureBox::Border, MeasureBox::Border)
+  }
 
-    let abs = get_elem_size(elem.as_ref(), lateral);
-    let par = get_elem_size(elem.as_ref().parent_element().unwrap_or(gloo_utils::document_element()), lateral);
+  /// Build a new Sizon from a given element,
+  /// measuring the element's `measure` box and its parent's `par_measure` box. (See [`MeasureBox`])
+  /// 
+  /// Ex. css percent sizes of statically positioned elements refer to their parent's content box.
+  pub fn elem_box<E: AsRef<Element>>(elem: E, lateral: bool, measure: MeasureBox, par_measure: MeasureBox) -> Self {
+
+    let abs = get_box_size(elem.as_ref(), lateral, measure);
+    let par = get_box_size(get_par_elem(elem), lateral, par_measure);
 
     let rel = if par.is_normal() { Some(abs/par) } else { None };
 
@@ -209,4 +283,44 @@ impl Sizon {
       None
     }
   }
-}
\ No newline at end of file
+}
+
+
+impl FromStr for Sizon {
+  type Err = Error;
+
+  /// Parse a css length literal.
+  /// * `"20px"` => `Sizon::abs(20.)`
+  /// * `"35%"` => `Sizon::rel(0.35)`
+  /// * `"0.35"` => `Sizon::rel(0.35)` (pure ratio)
+  /// * `"calc(35% + 20px)"` => `Sizon::new(Some(20.), Some(0.35))`
+  ///   - Each term fills its field. Terms of the same kind are summed up.
+  ///   - Just like css, `+` and `-` operators should be surrounded by whitespaces.
+  /// 
+  /// It shares the parser with [`UnitSizon`], only the absolute part should be in pixel.
+  /// 
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// assert_eq!("35%".parse::<Sizon>().unwrap(), Sizon::rel(0.35));
+  /// assert_eq!("0.35".parse::<Sizon>().unwrap(), Sizon::rel(0.35));
+  /// assert_eq!(" calc(20px + 35%) ".parse::<Sizon>().unwrap(), Sizon::new(Some(20.), Some(0.35)));
+  /// assert_eq!(
+  ///   "calc(50% - 12px)".parse::<Sizon>().unwrap(),
+  ///   "calc(50% - 12px)".parse::<UnitSizon>().unwrap().try_into().unwrap()
+  /// );
+  /// assert!("20em".parse::<Sizon>().is_err());
+  /// assert!("calc(35% -20px)".parse::<Sizon>().is_err());
+  /// ```
+  fn from_str(s: &str) -> Result<Self> {
+    s.parse::<UnitSizon>()?.try_into()
+  }
+}
+
+impl TryFrom<&str> for Sizon {
+  type Error = Error;
+
+  fn try_from(s: &str) -> Result<Self> {
+    s.parse()
+  }
+}
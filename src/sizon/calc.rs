@@ -0,0 +1,149 @@
+//! Sum of relative and absolute sizes, like css's `calc(50% - 12px)`
+//!
+//! [Sizon]'s `abs` and `rel` are *alternatives*: a limitation in px, or in ratio, or both of them to be compared.
+//! [`CalcSizon`] is a different thing: its `abs` and `rel` are *summed up*.
+//! So it can be added, subtracted, negated and scaled by `f64`, and be rendered into a `calc()` style value.
+//!
+//! It doesn't mix into Sizon implicitly.
+//! Resolve it with the parent's size by [`CalcSizon::to_abs()`] or [`CalcSizon::to_sizon()`].
+//! ```
+//! # use webtric::*;
+//! // half the parent minus the gutter
+//! let half = CalcSizon::rel(1.) * 0.5 - CalcSizon::abs(12.);
+//! assert_eq!(half, CalcSizon::new(-12., 0.5));
+//! assert_eq!(half.style_value(), "calc(50.00% - 12.00px)");
+//! assert_eq!(half.to_abs(200.), 88.);
+//! assert_eq!(half.to_sizon(200.), Sizon::new(Some(88.), Some(0.44)));
+//! ```
+
+use super::*;
+use std::ops::{Add, Sub, Neg, Mul, Div};
+
+/// Sum of `abs`(in pixel) and `rel`(ratio to the parent's size). Check [mod](crate::sizon::calc).
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct CalcSizon {
+  /// absolute part in pixel
+  pub abs: f64,
+  /// relative part as a ratio
+  pub rel: f64,
+}
+
+impl CalcSizon {
+
+  pub fn new(abs: f64, rel: f64) -> Self {
+    Self { abs, rel }
+  }
+
+  pub fn abs(abs: f64) -> Self {
+    Self { abs, rel: 0. }
+  }
+
+  pub fn rel(rel: f64) -> Self {
+    Self { abs: 0., rel }
+  }
+
+  /// Get the summed up size in pixel with given parent's size(`par`).
+  pub fn to_abs(&self, par: f64) -> f64 {
+    self.abs + self.rel*par
+  }
+
+  /// Resolve into a plain [Sizon] with given parent's size(`par`).
+  /// Both of its fields express the same summed up size, like [`Sizon::elem()`] does.
+  pub fn to_sizon(&self, par: f64) -> Sizon {
+    let abs = self.to_abs(par);
+    let rel = if par.is_normal() { Some(abs/par) } else { None };
+    Sizon::new(Some(abs), rel)
+  }
+
+  /// Returns size style formated value.
+  /// `calc()` literal only when both parts are non-zero. Otherwise px or % literal.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// assert_eq!((CalcSizon::rel(0.5) + CalcSizon::abs(12.)).style_value(), "calc(50.00% + 12.00px)");
+  /// assert_eq!(CalcSizon::rel(0.5).style_value(), "50.00%");
+  /// assert_eq!(CalcSizon::default().style_value(), "0.00px");
+  /// ```
+  pub fn style_value(&self) -> String {
+    match (self.abs!=0., self.rel!=0.) {
+      (true, true) => {
+        let sign = if self.abs<0. { '-' } else { '+' };
+        format!("calc({:.2}% {} {:.2}px)", self.rel*100., sign, self.abs.abs())
+      },
+      (false, true) => format!("{:.2}%", self.rel*100.),
+      _ => format!("{:.2}px", self.abs),
+    }
+  }
+
+  /// set size style property. Check [`style_value()`](Self::style_value).
+  pub fn set_style<E: AsRef<HtmlElement>>(&self, elem: E, lateral: bool) {
+    let property = if lateral {"width"} else {"height"};
+    elem.as_ref().style().set_property(property, self.style_value().as_str()).unwrap_throw();
+  }
+}
+
+impl FromStr for CalcSizon {
+  type Err = Error;
+
+  /// Parse a css length literal. Check [`Sizon::from_str()`], they share the parser.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// assert_eq!("calc(50% - 12px)".parse::<CalcSizon>().unwrap(), CalcSizon::new(-12., 0.5));
+  /// assert_eq!("20px".parse::<CalcSizon>().unwrap(), CalcSizon::abs(20.));
+  /// ```
+  fn from_str(s: &str) -> Result<Self> {
+    let sizon: Sizon = s.parse()?;
+    Ok(Self::new(sizon.abs.unwrap_or_default(), sizon.rel.unwrap_or_default()))
+  }
+}
+
+impl Add for CalcSizon {
+  type Output = Self;
+
+  fn add(self, rhs: Self) -> Self {
+    Self { abs: self.abs+rhs.abs, rel: self.rel+rhs.rel }
+  }
+}
+
+impl Sub for CalcSizon {
+  type Output = Self;
+
+  fn sub(self, rhs: Self) -> Self {
+    self + (-rhs)
+  }
+}
+
+impl Neg for CalcSizon {
+  type Output = Self;
+
+  fn neg(self) -> Self {
+    Self { abs: -self.abs, rel: -self.rel }
+  }
+}
+
+impl Mul<f64> for CalcSizon {
+  type Output = Self;
+
+  fn mul(self, rhs: f64) -> Self {
+    Self { abs: self.abs*rhs, rel: self.rel*rhs }
+  }
+}
+
+impl Mul<CalcSizon> for f64 {
+  type Output = CalcSizon;
+
+  fn mul(self, rhs: CalcSizon) -> CalcSizon {
+    rhs * self
+  }
+}
+
+impl Div<f64> for CalcSizon {
+  type Output = Self;
+
+  fn div(self, rhs: f64) -> Self {
+    Self { abs: self.abs/rhs, rel: self.rel/rhs }
+  }
+}
@@ -0,0 +1,363 @@
+//! Font and viewport relative units for [Sizon]
+//!
+//! [Sizon]'s `abs` field only knows pixels. However, sizes like minimum limits of panels
+//! often need to scale with user's font size (`em`, `rem`), or with the viewport (`vw`, `vh`, `vmin`, `vmax`).
+//!
+//! * [`Length`] is a length value with its [`LengthUnit`].
+//! * [`UnitSizon`] is a Sizon whose `abs` field is a [`Length`].
+//! * [`SizonContext`] carries font sizes and viewport size at the moment, so as to resolve them into pixels.
+//!
+//! Resolve a `UnitSizon` into a plain [Sizon] with the context, then use it as usual.
+//! For example, [`CartonsComplex`]'s `min` map can be rebuilt from a `CartonsMap<T, UnitSizon>`
+//! whenever the font size or the viewport changes.
+//! ```
+//! # use webtric::*;
+//! let ctx = SizonContext::new(16., 20., 1000., 800.);
+//!
+//! let min: UnitSizon = "10em".parse().unwrap();
+//! assert_eq!(min.resolve(&ctx), Sizon::abs(200.));
+//!
+//! let mins: CartonsMap<usize, UnitSizon> = (vec![(0, min)], UnitSizon::abs(Length::rem(5.))).into();
+//! let mins: CartonsMap<usize, Sizon> = mins.resolve(&ctx);
+//! assert_eq!(*mins.get(&1), Sizon::abs(80.));
+//! ```
+
+use super::*;
+
+/// Unit of a [`Length`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LengthUnit {
+  /// pixel
+  Px,
+  /// relative to the element's font size
+  Em,
+  /// relative to the root element's font size
+  Rem,
+  /// 1% of the viewport's width
+  Vw,
+  /// 1% of the viewport's height
+  Vh,
+  /// 1% of the viewport's smaller dimension
+  Vmin,
+  /// 1% of the viewport's larger dimension
+  Vmax,
+}
+
+impl LengthUnit {
+
+  /// css unit literal, like "px" or "em"
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Px => "px",
+      Self::Em => "em",
+      Self::Rem => "rem",
+      Self::Vw => "vw",
+      Self::Vh => "vh",
+      Self::Vmin => "vmin",
+      Self::Vmax => "vmax",
+    }
+  }
+
+  /// How many pixels does one unit take in the given context?
+  pub fn px(&self, ctx: &SizonContext) -> f64 {
+    match self {
+      Self::Px => 1.,
+      Self::Em => ctx.font_size,
+      Self::Rem => ctx.root_font_size,
+      Self::Vw => ctx.viewport_width/100.,
+      Self::Vh => ctx.viewport_height/100.,
+      Self::Vmin => ctx.viewport_width.min(ctx.viewport_height)/100.,
+      Self::Vmax => ctx.viewport_width.max(ctx.viewport_height)/100.,
+    }
+  }
+}
+
+
+/// Resolution context of [`Length`] and [`UnitSizon`]
+///
+/// All values are in pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SizonContext {
+  /// root element's font size (for `rem`)
+  pub root_font_size: f64,
+  /// element's font size (for `em`)
+  pub font_size: f64,
+  /// viewport's width (for `vw`, `vmin`, `vmax`)
+  pub viewport_width: f64,
+  /// viewport's height (for `vh`, `vmin`, `vmax`)
+  pub viewport_height: f64,
+}
+
+impl Default for SizonContext {
+  /// Browsers' usual default font size(16px) with zero-sized viewport
+  fn default() -> Self {
+    Self { root_font_size: 16., font_size: 16., viewport_width: 0., viewport_height: 0. }
+  }
+}
+
+impl SizonContext {
+
+  pub fn new(root_font_size: f64, font_size: f64, viewport_width: f64, viewport_height: f64) -> Self {
+    Self { root_font_size, font_size, viewport_width, viewport_height }
+  }
+
+  /// Measure context from the document. `em` will be same with `rem`.
+  pub fn root() -> Self {
+    let root_font_size = get_font_size(gloo_utils::document_element()).unwrap_or(16.);
+    let (viewport_width, viewport_height) = get_viewport_size();
+    Self { root_font_size, font_size: root_font_size, viewport_width, viewport_height }
+  }
+
+  /// Measure context from the document and the given element's computed font size.
+  pub fn elem<E: AsRef<Element>>(elem: E) -> Self {
+    let ctx = Self::root();
+    let font_size = get_font_size(elem).unwrap_or(ctx.root_font_size);
+    Self { font_size, ..ctx }
+  }
+}
+
+/// Get computed font size of an element in pixel
+fn get_font_size<E: AsRef<Element>>(elem: E) -> Option<f64> {
+  get_computed_px(elem, "font-size")
+}
+
+/// Get window's inner width and height, which `vw` and `vh` refer to.
+fn get_viewport_size() -> (f64, f64) {
+  let window = gloo_utils::window();
+  let width = window.inner_width().ok().and_then(|x| x.as_f64()).unwrap_or_default();
+  let height = window.inner_height().ok().and_then(|x| x.as_f64()).unwrap_or_default();
+  (width, height)
+}
+
+
+/// A length value with its unit
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Length {
+  pub value: f64,
+  pub unit: LengthUnit,
+}
+
+impl Length {
+
+  pub fn new(value: f64, unit: LengthUnit) -> Self {
+    Self { value, unit }
+  }
+
+  pub fn px(value: f64) -> Self { Self::new(value, LengthUnit::Px) }
+  pub fn em(value: f64) -> Self { Self::new(value, LengthUnit::Em) }
+  pub fn rem(value: f64) -> Self { Self::new(value, LengthUnit::Rem) }
+  pub fn vw(value: f64) -> Self { Self::new(value, LengthUnit::Vw) }
+  pub fn vh(value: f64) -> Self { Self::new(value, LengthUnit::Vh) }
+  pub fn vmin(value: f64) -> Self { Self::new(value, LengthUnit::Vmin) }
+  pub fn vmax(value: f64) -> Self { Self::new(value, LengthUnit::Vmax) }
+
+  /// Resolve into pixel with given context
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// let ctx = SizonContext::new(16., 20., 1000., 800.);
+  /// assert_eq!(Length::em(2.).to_px(&ctx), 40.);
+  /// assert_eq!(Length::rem(2.).to_px(&ctx), 32.);
+  /// assert_eq!(Length::vmin(10.).to_px(&ctx), 80.);
+  /// ```
+  pub fn to_px(&self, ctx: &SizonContext) -> f64 {
+    self.value * self.unit.px(ctx)
+  }
+
+  /// Returns style formated value. Ex. "2.00em"
+  pub fn style_value(&self) -> String {
+    format!("{:.2}{}", self.value, self.unit.as_str())
+  }
+}
+
+impl FromStr for Length {
+  type Err = Error;
+
+  /// Parse a css length literal with its unit, like "20px" or "1.5rem"
+  fn from_str(s: &str) -> Result<Self> {
+    let s = s.trim();
+    [LengthUnit::Rem, LengthUnit::Em, LengthUnit::Px, LengthUnit::Vmin, LengthUnit::Vmax, LengthUnit::Vw, LengthUnit::Vh]
+      .into_iter()
+      .find_map(|unit| {
+        s.strip_suffix(unit.as_str())
+          .and_then(|value| value.parse::<f64>().ok())
+          .filter(|value| value.is_finite())
+          .map(|value| Self::new(value, unit))
+      })
+      .ok_or_else(|| Error::Msg(format!("invalid length literal: {:?}", s)))
+  }
+}
+
+
+/// [Sizon] whose `abs` field is a [`Length`] of any [`LengthUnit`]
+///
+/// Resolve it into a plain [Sizon] with a [`SizonContext`].
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct UnitSizon {
+  /// absolute size with unit
+  pub abs: Option<Length>,
+  /// relative size raito
+  pub rel: Option<f64>,
+}
+
+impl From<Sizon> for UnitSizon {
+  fn from(sizon: Sizon) -> Self {
+    Self { abs: sizon.abs.map(Length::px), rel: sizon.rel }
+  }
+}
+
+impl UnitSizon {
+
+  pub fn new(abs: Option<Length>, rel: Option<f64>) -> Self {
+    Self { abs, rel }
+  }
+
+  pub fn abs(abs: Length) -> Self {
+    Self { abs: Some(abs), rel: None }
+  }
+
+  pub fn rel(rel: f64) -> Self {
+    Self { abs: None, rel: Some(rel) }
+  }
+
+  /// Resolve into a plain [Sizon] with given context
+  pub fn resolve(&self, ctx: &SizonContext) -> Sizon {
+    Sizon { abs: self.abs.map(|abs| abs.to_px(ctx)), rel: self.rel }
+  }
+
+  /// Check [`Sizon::to_abs()`]
+  pub fn to_abs(&self, par: f64, ctx: &SizonContext) -> Option<f64> {
+    self.resolve(ctx).to_abs(par)
+  }
+
+  /// Check [`Sizon::max()`]
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// let ctx = SizonContext::new(16., 20., 1000., 800.);
+  /// let sizon = UnitSizon::new(Some(Length::em(2.)), Some(0.5));
+  /// assert_eq!(sizon.max(30., None, &ctx), 40.);
+  /// assert_eq!(sizon.max(30., Some(100.), &ctx), 50.);
+  /// ```
+  pub fn max(&self, abs: f64, par: Option<f64>, ctx: &SizonContext) -> f64 {
+    self.resolve(ctx).max(abs, par)
+  }
+
+  /// Check [`Sizon::min()`]
+  pub fn min(&self, abs: f64, par: Option<f64>, ctx: &SizonContext) -> f64 {
+    self.resolve(ctx).min(abs, par)
+  }
+
+  /// Build a new UnitSizon from a given element, expressing its `abs` in given `unit`.
+  /// Check [`Sizon::elem()`]
+  pub fn elem<E: AsRef<Element>>(elem: E, lateral: bool, unit: LengthUnit, ctx: &SizonContext) -> Self {
+    let sizon = Sizon::elem(elem, lateral);
+    let px = unit.px(ctx);
+    let abs = sizon.abs.filter(|_| px.is_normal()).map(|abs| Length::new(abs/px, unit));
+    Self { abs, rel: sizon.rel }
+  }
+}
+
+impl TryFrom<UnitSizon> for Sizon {
+  type Error = Error;
+
+  /// Only when its `abs` is in pixel, or none.
+  fn try_from(sizon: UnitSizon) -> Result<Self> {
+    match sizon.abs {
+      Some(abs) if abs.unit!=LengthUnit::Px => Err(Error::Msg(format!("not a pixel length: {:?}", abs.style_value()))),
+      abs => Ok(Sizon::new(abs.map(|abs| abs.value), sizon.rel))
+    }
+  }
+}
+
+impl FromStr for UnitSizon {
+  type Err = Error;
+
+  /// Parse a css length literal.
+  /// It's the parser behind [`Sizon::from_str()`] and [`CalcSizon::from_str()`] as well.
+  /// * a [`Length`] of any [`LengthUnit`] => `abs`. Ex. "20px", "2em"
+  /// * percent, or a pure ratio => `rel`. Ex. "35%", "0.35"
+  /// * `calc()` of them, Ex. "calc(50% - 1.5rem)"
+  ///   - Terms of the same kind are summed up. Absolute terms should share a unit.
+  ///   - Just like css, `+` and `-` operators should be surrounded by whitespaces.
+  ///
+  /// # Example
+  /// ```
+  /// # use webtric::*;
+  /// assert_eq!("2em".parse::<UnitSizon>().unwrap(), UnitSizon::abs(Length::em(2.)));
+  /// assert_eq!("35%".parse::<UnitSizon>().unwrap(), UnitSizon::rel(0.35));
+  /// assert_eq!(
+  ///   "calc(50% - 1.5rem)".parse::<UnitSizon>().unwrap(),
+  ///   UnitSizon::new(Some(Length::rem(-1.5)), Some(0.5))
+  /// );
+  /// assert!("calc(1em + 1rem)".parse::<UnitSizon>().is_err());
+  /// ```
+  fn from_str(s: &str) -> Result<Self> {
+    let s = s.trim();
+    let err = || Error::Msg(format!("invalid sizon literal: {:?}", s));
+
+    let parse_term = |s: &str| -> Option<Self> {
+      if let Ok(abs) = s.parse::<Length>() {
+        Some(Self::abs(abs))
+      } else {
+        parse_rel(s).map(Self::rel)
+      }
+    };
+
+    let Some(inner) = s.strip_prefix("calc(").and_then(|s| s.strip_suffix(')')) else {
+      return parse_term(s).ok_or_else(err);
+    };
+
+    let mut sizon = Self::default();
+    let mut sign = Some(1.);
+    for token in inner.split_whitespace() {
+      // expecting an operator
+      let Some(sign_) = sign.take() else {
+        sign = match token { "+" => Some(1.), "-" => Some(-1.), _ => return Err(err()) };
+        continue;
+      };
+      let term = parse_term(token).ok_or_else(err)?;
+      if let Some(rel) = term.rel {
+        sizon.rel = Some(sizon.rel.unwrap_or_default() + sign_*rel);
+      }
+      if let Some(abs) = term.abs {
+        sizon.abs = match sizon.abs {
+          None => Some(Length::new(sign_*abs.value, abs.unit)),
+          Some(x) if x.unit==abs.unit => Some(Length::new(x.value + sign_*abs.value, x.unit)),
+          Some(_) => return Err(err())
+        };
+      }
+    }
+    // empty, or dangling operator
+    if sign.is_some() {
+      return Err(err());
+    }
+    Ok(sizon)
+  }
+}
+
+/// Parse a relative term: "35%" or a pure ratio "0.35"
+fn parse_rel(s: &str) -> Option<f64> {
+  let parse = |s: &str| s.parse::<f64>().ok().filter(|x| x.is_finite());
+  if let Some(percent) = s.strip_suffix('%') {
+    parse(percent).map(|x| x/100.)
+  } else {
+    parse(s)
+  }
+}
+
+
+impl<T: Eq + Hash + FromStr + Clone> CartonsMap<T, UnitSizon> {
+
+  /// Resolve every [`UnitSizon`] into a plain [Sizon] with given context.
+  /// Use it to (re)build [`CartonsComplex`]'s `min`, `max` and `zeroed_when` maps.
+  pub fn resolve(&self, ctx: &SizonContext) -> CartonsMap<T, Sizon> {
+    CartonsMap {
+      map: self.map.iter().map(|(data, sizon)| (data.clone(), sizon.resolve(ctx))).collect(),
+      default: self.default.resolve(ctx)
+    }
+  }
+}
@@ -0,0 +1,341 @@
+//! # Space Solver: distribute space with Sizon limits
+//!
+//! Parallel things in a container, like cartons, table columns or toolbar items,
+//! usually need to share the container's space while keeping their own minimum and maximum limits.
+//!
+//! This module solves it without any DOM access, so it runs on any target.
+//! * [`SpaceItem`] is an item's current size, [`Sizon`] limitations and weight.
+//! * [`SpaceSolver`] takes a container's size and items, then returns resolved sizes.
+//!   Its [`Distribution`] decides how items share the space by their weights.
+//!
+//! [`CartonsComplex`] uses it to fill blank space of its wrap.
+//!
+//! # Example
+//! ```
+//! # use webtric::*;
+//! // weights are current sizes, like cartons
+//! let items = vec![
+//!   SpaceItem::new(Some(100.), Sizon::abs(50.), Sizon::default(), 100.),
+//!   SpaceItem::new(None, Sizon::default(), Sizon::default(), 100.), // zeroed: takes no space
+//!   SpaceItem::new(Some(100.), Sizon::default(), Sizon::rel(0.3), 100.),
+//! ];
+//!
+//! // grow: the last one is limited by its max(30%), so the first one takes the rest.
+//! let sizes = SpaceSolver::new(500.).solve(&items);
+//! assert_eq!(sizes, vec![Some(350.), None, Some(150.)]);
+//!
+//! // shrink: the first one is limited by its min(50px), so the last one gives up the rest.
+//! let items = vec![
+//!   SpaceItem::new(Some(100.), Sizon::abs(50.), Sizon::default(), 100.),
+//!   SpaceItem::new(Some(100.), Sizon::default(), Sizon::default(), 100.),
+//! ];
+//! let sizes = SpaceSolver::new(80.).solve(&items);
+//! assert_eq!(sizes, vec![Some(50.), Some(30.)]);
+//! ```
+
+use crate::*;
+
+/// An item sharing space of a container
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct SpaceItem {
+  /// current size. None refers to "zeroed" state, which takes no space.
+  pub size: Option<f64>,
+  /// minimum size limit
+  pub min: Sizon,
+  /// maximum size limit
+  pub max: Sizon,
+  /// share of growing or shrinking.
+  /// * Items with zero weight only take leftovers.
+  pub weight: f64,
+}
+
+impl SpaceItem {
+
+  pub fn new(size: Option<f64>, min: Sizon, max: Sizon, weight: f64) -> Self {
+    Self { size, min, max, weight }
+  }
+
+  /// return max limited size
+  pub fn max_limited(&self, size: f64, container: f64) -> f64 {
+    self.max.min(size, Some(container))
+  }
+
+  /// return min and max limited size
+  pub fn limited(&self, size: f64, container: f64) -> f64 {
+    let size = self.max.min(size, Some(container));
+    self.min.max(size, Some(container))
+  }
+}
+
+
+/// How to distribute blank or overflowing space among items, before the rest goes to rear ones.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Distribution {
+  /// From front ones, each item takes `weight/container`(up to all) of the space left at its turn.
+  /// With sizes as weights, it's how [`CartonsComplex`] has filled blank space.
+  #[default]
+  Sequential,
+  /// Each item takes `weight/total_weight` of the whole space.
+  Proportional,
+}
+
+/// Resolve sizes of [`SpaceItem`]s in a container
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpaceSolver {
+  /// container's size
+  pub container: f64,
+  /// fill blank space when items are smaller than the container
+  pub grow: bool,
+  /// cut overflowing space when items are larger than the container
+  pub shrink: bool,
+  /// how to distribute space. (See [`Distribution`])
+  pub distribution: Distribution,
+}
+
+impl SpaceSolver {
+
+  /// New solver which both grows and shrinks, with `Sequential` distribution.
+  pub fn new(container: f64) -> Self {
+    Self { container, grow: true, shrink: true, distribution: Distribution::Sequential }
+  }
+
+  /// Set distribution
+  pub fn distribution(mut self, distribution: Distribution) -> Self {
+    self.distribution = distribution;
+    self
+  }
+
+  /// Total size of given sizes. Zeroed ones are ignored.
+  pub fn total_size(sizes: &[Option<f64>]) -> f64 {
+    sizes.iter().flatten().sum()
+  }
+
+  /// Resolve sizes:
+  /// 1. limit each item's size with its min and max
+  /// 2. fill blank space (`grow`)
+  /// 3. cut overflowing space (`shrink`)
+  ///
+  /// Items' order is preserved and zeroed items stay None.
+  pub fn solve(&self, items: &[SpaceItem]) -> Vec<Option<f64>> {
+    let mut sizes: Vec<Option<f64>> = items.iter().map(|item| {
+      item.size.map(|size| item.limited(size, self.container))
+    }).collect();
+
+    if self.grow {
+      self.fill_blank(items, &mut sizes);
+    }
+    if self.shrink {
+      self.cut_overflow(items, &mut sizes);
+    }
+    sizes
+  }
+
+  /// Adjust `sizes` to fill blank space, not exceeding each item's max limitation.
+  /// Return adjusted total size.
+  ///
+  /// 1. distribution by weight (See [`Distribution`])
+  /// 2. distribute the rest from rear ones
+  pub fn fill_blank(&self, items: &[SpaceItem], sizes: &mut [Option<f64>]) -> f64 {
+    let mut total_size = Self::total_size(sizes);
+
+    let blank = (self.container-total_size).floor();
+    if blank>0. {
+      let grow = |item: &SpaceItem, size: &mut f64, add: f64| -> f64 {
+        let new_size = item.max_limited(*size+add, self.container);
+        let delta = (new_size-*size).max(0.);
+        *size += delta;
+        delta
+      };
+
+      // 1. distribution by weight
+      let mut rest = self.distribute(items, sizes, blank, grow);
+
+      // 2. distribute from rear ones
+      for (item, size) in items.iter().zip(sizes.iter_mut()).rev() {
+        if rest<=0. {
+          break;
+        }
+        if let Some(size) = size {
+          rest -= grow(item, size, rest);
+        }
+      }
+
+      total_size += blank-rest.max(0.);
+    }
+
+    total_size
+  }
+
+  /// Adjust `sizes` to cut overflowing space, not going under each item's min limitation.
+  /// Return adjusted total size.
+  ///
+  /// 1. cut by weight (See [`Distribution`])
+  /// 2. cut the rest from rear ones
+  pub fn cut_overflow(&self, items: &[SpaceItem], sizes: &mut [Option<f64>]) -> f64 {
+    let mut total_size = Self::total_size(sizes);
+
+    let overflow = (total_size-self.container).floor();
+    if overflow>0. {
+      let shrink = |item: &SpaceItem, size: &mut f64, cut: f64| -> f64 {
+        let min = item.min.min_limit(self.container).max(0.);
+        let delta = cut.min(*size-min).max(0.);
+        *size -= delta;
+        delta
+      };
+
+      // 1. cut by weight
+      let mut rest = self.distribute(items, sizes, overflow, shrink);
+
+      // 2. cut from rear ones
+      for (item, size) in items.iter().zip(sizes.iter_mut()).rev() {
+        if rest<=0. {
+          break;
+        }
+        if let Some(size) = size {
+          rest -= shrink(item, size, rest);
+        }
+      }
+
+      total_size -= overflow-rest.max(0.);
+    }
+
+    total_size
+  }
+
+  /// Shares of `space` by items at step 1 of [`fill_blank()`](Self::fill_blank) and [`cut_overflow()`](Self::cut_overflow).
+  /// `take` gets an item's share and returns how much it took.
+  /// Return the rest of space.
+  fn distribute(
+    &self,
+    items: &[SpaceItem],
+    sizes: &mut [Option<f64>],
+    space: f64,
+    take: impl Fn(&SpaceItem, &mut f64, f64) -> f64
+  ) -> f64 {
+    let mut rest = space;
+    match self.distribution {
+      Distribution::Sequential => {
+        if self.container>0. {
+          for (item, size) in items.iter().zip(sizes.iter_mut()) {
+            if rest<=0. {
+              break;
+            }
+            if let Some(size) = size {
+              rest -= take(item, size, (rest*item.weight.max(0.)/self.container).min(rest));
+            }
+          }
+        }
+      },
+      Distribution::Proportional => {
+        let total_weight = Self::total_weight(items, sizes);
+        if total_weight>0. {
+          for (item, size) in items.iter().zip(sizes.iter_mut()) {
+            if let Some(size) = size {
+              rest -= take(item, size, space*item.weight.max(0.)/total_weight);
+            }
+          }
+        }
+      },
+    }
+    rest
+  }
+
+  /// Total weight of not zeroed items
+  fn total_weight(items: &[SpaceItem], sizes: &[Option<f64>]) -> f64 {
+    items.iter().zip(sizes.iter())
+      .filter(|(_, size)| size.is_some())
+      .map(|(item, _)| item.weight.max(0.))
+      .sum()
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn item(size: f64) -> SpaceItem {
+    SpaceItem::new(Some(size), Sizon::default(), Sizon::default(), size)
+  }
+
+  #[test]
+  fn sequential_fill_blank() {
+    // front ones take `size/container` of the blank left at their turn, and the rest goes to the rear one.
+    let items = vec![item(100.), item(100.)];
+    let mut sizes = vec![Some(100.), Some(100.)];
+    let total = SpaceSolver::new(1000.).fill_blank(&items, &mut sizes);
+    assert_eq!(total, 1000.);
+    assert_eq!(sizes, vec![Some(180.), Some(820.)]);
+  }
+
+  #[test]
+  fn proportional_fill_blank() {
+    let items = vec![item(100.), item(300.)];
+    let mut sizes = vec![Some(100.), Some(300.)];
+    let total = SpaceSolver::new(800.).distribution(Distribution::Proportional).fill_blank(&items, &mut sizes);
+    assert_eq!(total, 800.);
+    assert_eq!(sizes, vec![Some(200.), Some(600.)]);
+  }
+
+  #[test]
+  fn zero_or_negative_container() {
+    let items = vec![item(100.), item(100.)];
+    for distribution in [Distribution::Sequential, Distribution::Proportional] {
+      let sizes = SpaceSolver::new(0.).distribution(distribution).solve(&items);
+      assert_eq!(sizes, vec![Some(0.), Some(0.)]);
+
+      let sizes = SpaceSolver::new(-50.).distribution(distribution).solve(&items);
+      assert_eq!(sizes, vec![Some(0.), Some(0.)]);
+    }
+  }
+
+  #[test]
+  fn all_zeroed() {
+    let items = vec![SpaceItem::new(None, Sizon::default(), Sizon::default(), 1.); 3];
+    for distribution in [Distribution::Sequential, Distribution::Proportional] {
+      let solver = SpaceSolver::new(500.).distribution(distribution);
+      let mut sizes = vec![None; 3];
+      assert_eq!(solver.fill_blank(&items, &mut sizes), 0.);
+      assert_eq!(solver.cut_overflow(&items, &mut sizes), 0.);
+      assert_eq!(sizes, vec![None; 3]);
+    }
+  }
+
+  #[test]
+  fn max_limits_absorb_blank() {
+    // every item is limited by its max, so the blank is left.
+    let items = vec![
+      SpaceItem::new(Some(100.), Sizon::default(), Sizon::abs(150.), 100.),
+      SpaceItem::new(Some(100.), Sizon::default(), Sizon::rel(0.2), 100.),
+    ];
+    for distribution in [Distribution::Sequential, Distribution::Proportional] {
+      let solver = SpaceSolver::new(1000.).distribution(distribution);
+      let mut sizes = vec![Some(100.), Some(100.)];
+      assert_eq!(solver.fill_blank(&items, &mut sizes), 350.);
+      assert_eq!(sizes, vec![Some(150.), Some(200.)]);
+    }
+  }
+
+  #[test]
+  fn min_limits_cut_overflow() {
+    let items = vec![
+      SpaceItem::new(Some(300.), Sizon::abs(250.), Sizon::default(), 300.),
+      SpaceItem::new(Some(300.), Sizon::rel(0.4), Sizon::default(), 300.),
+    ];
+    for distribution in [Distribution::Sequential, Distribution::Proportional] {
+      let solver = SpaceSolver::new(500.).distribution(distribution);
+
+      // 100px overflow: cut while keeping mins(250px, 200px)
+      let mut sizes = vec![Some(300.), Some(300.)];
+      assert_eq!(solver.cut_overflow(&items, &mut sizes), 500.);
+      assert_eq!(sizes.iter().flatten().sum::<f64>(), 500.);
+      assert!(sizes[0].unwrap()>=250. && sizes[1].unwrap()>=200.);
+
+      // mins can't be cut: overflow is left
+      let solver = SpaceSolver::new(400.).distribution(distribution);
+      let mut sizes = vec![Some(300.), Some(300.)];
+      assert_eq!(solver.cut_overflow(&items, &mut sizes), 410.);
+      assert_eq!(sizes, vec![Some(250.), Some(160.)]);
+    }
+  }
+}
@@ -185,6 +185,143 @@ pub fn get_elem_front_and_size<E: AsRef<Element>>(elem: E, lateral: bool) -> (f6
 }
 
 
+/// Plain rectangle in client(viewport) coordinates.
+/// 
+/// It's a DOM-free copy of DomRect, so that metric computations using it can run on any target.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+  pub left: f64,
+  pub top: f64,
+  pub width: f64,
+  pub height: f64
+}
+
+impl From<web_sys::DomRect> for Rect {
+  fn from(rect: web_sys::DomRect) -> Self {
+    Self::new(rect.left(), rect.top(), rect.width(), rect.height())
+  }
+}
+
+impl Rect {
+
+  pub fn new(left: f64, top: f64, width: f64, height: f64) -> Self {
+    Self { left, top, width, height }
+  }
+
+  /// Element's DomRect (getBoundingClientRect)
+  pub fn elem<E: AsRef<Element>>(elem: E) -> Self {
+    elem.as_ref().get_bounding_client_rect().into()
+  }
+
+  /// Browser(document)'s client area
+  pub fn document() -> Self {
+    let doc = gloo_utils::document_element();
+    Self::new(0., 0., doc.client_width() as f64, doc.client_height() as f64)
+  }
+
+  pub fn right(&self) -> f64 { self.left + self.width }
+
+  pub fn bottom(&self) -> f64 { self.top + self.height }
+
+  /// lateral(true/false) -> left/top
+  pub fn front(&self, lateral: bool) -> f64 {
+    if lateral { self.left } else { self.top }
+  }
+
+  /// lateral(true/false) -> right/bottom
+  pub fn rear(&self, lateral: bool) -> f64 {
+    if lateral { self.right() } else { self.bottom() }
+  }
+
+  /// lateral(true/false) -> width/height
+  pub fn size(&self, lateral: bool) -> f64 {
+    if lateral { self.width } else { self.height }
+  }
+
+  /// Return moved rect by (x, y)
+  pub fn translate(&self, x: f64, y: f64) -> Self {
+    Self { left: self.left + x, top: self.top + y, ..*self }
+  }
+
+  /// Return shrinked rect by `padding` for every side. Negative `padding` expands it.
+  pub fn inset(&self, padding: f64) -> Self {
+    Self::new(self.left + padding, self.top + padding, (self.width - 2.*padding).max(0.), (self.height - 2.*padding).max(0.))
+  }
+
+  /// Return overlapping area of two rects. Its size would be zero when they don't overlap.
+  /// 
+  /// # Example
+  /// ```
+  /// # use webtric::utils::Rect;
+  /// let a = Rect::new(0., 0., 100., 100.);
+  /// let b = Rect::new(50., 80., 100., 100.);
+  /// assert_eq!(a.intersect(&b), Rect::new(50., 80., 50., 20.));
+  /// ```
+  pub fn intersect(&self, other: &Self) -> Self {
+    let left = self.left.max(other.left);
+    let top = self.top.max(other.top);
+    let width = (self.right().min(other.right()) - left).max(0.);
+    let height = (self.bottom().min(other.bottom()) - top).max(0.);
+    Self { left, top, width, height }
+  }
+
+  /// Does the rect contain the point (x, y)?
+  pub fn contains(&self, x: f64, y: f64) -> bool {
+    self.left<=x && x<=self.right() && self.top<=y && y<=self.bottom()
+  }
+}
+
+/// Which box of css box model to measure
+/// 
+/// Using [`get_box_size()`], `Content` and `Padding` are computed from clientWidth/clientHeight,
+/// so they exclude scrollbars and transform scaling, just like the boxes css percent sizes refer to.
+/// `Border` and `Margin` are computed from DomRect size.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MeasureBox {
+  /// content box: excludes padding, border, scrollbar and margin
+  Content,
+  /// padding box: includes padding, excludes border, scrollbar and margin
+  Padding,
+  /// border box: DomRect size (getBoundingClientRect)
+  #[default]
+  Border,
+  /// margin box: includes margin
+  Margin,
+  /// clientWidth/clientHeight: padding box excluding scrollbar, in integer. Same with `Padding`
+  Client,
+}
+
+/// Get computed style's property value in pixel. Ex. "padding-left" => 10.
+pub fn get_computed_px<E: AsRef<Element>>(elem: E, property: &str) -> Option<f64> {
+  let style = gloo_utils::window().get_computed_style(elem.as_ref()).ok().flatten()?;
+  let value = style.get_property_value(property).ok()?;
+  value.trim().strip_suffix("px")?.parse().ok()
+}
+
+/// Get size of the element's given box: lateral(true/false) -> width/height
+pub fn get_box_size<E: AsRef<Element>>(elem: E, lateral: bool, measure: MeasureBox) -> f64 {
+  let elem = elem.as_ref();
+
+  let (front, rear) = if lateral { ("left", "right") } else { ("top", "bottom") };
+  let edges = |prop: &str| -> f64 {
+    [front, rear].iter()
+      .filter_map(|side| get_computed_px(elem, &format!("{}-{}", prop, side)))
+      .sum()
+  };
+
+  match measure {
+    MeasureBox::Content => get_client_size(elem, lateral) - edges("padding"),
+    MeasureBox::Padding | MeasureBox::Client => get_client_size(elem, lateral),
+    MeasureBox::Margin => get_elem_size(elem, lateral) + edges("margin"),
+    MeasureBox::Border => get_elem_size(elem, lateral),
+  }.max(0.)
+}
+
+/// `performance.now()` in milliseconds
+pub(crate) fn now() -> f64 {
+  gloo_utils::window().performance().map(|x| x.now()).unwrap_or(0.)
+}
+
 /// Style properties of size and front position:
 /// lateral(true/false) -> ("width", "left")/("height", "top")
 pub fn size_pos_props<'a>(lateral: bool) -> (&'a str, &'a str) {